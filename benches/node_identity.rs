@@ -0,0 +1,46 @@
+// Copyright 2021 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// https://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Compares `HashSet` lookups keyed by `PublicKey` and by `NodeIdentity`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sn_data_types::{Keypair, NodeIdentity, PublicKey};
+use std::collections::HashSet;
+
+const SET_SIZE: usize = 100;
+
+fn hash_set_lookups(c: &mut Criterion) {
+    let mut rng = rand::thread_rng();
+    let keys: Vec<PublicKey> = (0..SET_SIZE)
+        .map(|_| Keypair::new_ed25519(&mut rng).public_key())
+        .collect();
+    let key_set: HashSet<PublicKey> = keys.iter().copied().collect();
+    let identity_set: HashSet<NodeIdentity> =
+        keys.iter().copied().map(NodeIdentity::from).collect();
+
+    let mut group = c.benchmark_group("hash_set_lookups");
+    let _ = group.bench_function("PublicKey", |b| {
+        b.iter(|| {
+            keys.iter()
+                .filter(|key| key_set.contains(black_box(key)))
+                .count()
+        })
+    });
+    let _ = group.bench_function("NodeIdentity", |b| {
+        b.iter(|| {
+            keys.iter()
+                .filter(|key| identity_set.contains(black_box(&NodeIdentity::from(**key))))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hash_set_lookups);
+criterion_main!(benches);
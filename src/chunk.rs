@@ -9,7 +9,12 @@
 
 use crate::{utils, Error, PublicKey, XorName};
 use bincode::serialized_size;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+use rand::Rng;
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::{self, Debug, Formatter},
     u64,
@@ -18,6 +23,12 @@ use std::{
 /// Maximum allowed size for a serialised Chunk to grow to.
 pub const MAX_CHUNK_SIZE_IN_BYTES: u64 = 1024 * 1024 + 10 * 1024;
 
+/// Domain separator for the per-owner deduplication name of a Private Chunk.
+const PRIVATE_CHUNK_DEDUP_DOMAIN: &[u8] = b"sn_data_types::PrivateChunk::dedup_name";
+
+/// Length of the nonce prefixed to the ciphertext of an encrypted Private Chunk.
+const PRIVATE_CHUNK_NONCE_LEN: usize = 12;
+
 /// Private Chunk: an immutable chunk of data which can be deleted. Can only be fetched
 /// by the listed owner.
 #[derive(Hash, Eq, PartialEq, PartialOrd, Ord, Clone)]
@@ -34,7 +45,7 @@ pub struct PrivateChunk {
 impl PrivateChunk {
     /// Creates a new instance of `PrivateChunk`.
     pub fn new(value: Vec<u8>, owner: PublicKey) -> Self {
-        let address = Address::Private(XorName::from_content(&[&value, &owner.to_bytes()]));
+        let address = Self::address_of(&value, &owner);
 
         Self {
             address,
@@ -43,6 +54,70 @@ impl PrivateChunk {
         }
     }
 
+    /// Creates a new instance of `PrivateChunk`, or returns `Err(Error::ExceededSize)` without
+    /// building it if its serialised size would exceed `MAX_CHUNK_SIZE_IN_BYTES`.
+    /// A chunk of exactly `MAX_CHUNK_SIZE_IN_BYTES` is accepted, one byte more is rejected.
+    pub fn new_checked(value: Vec<u8>, owner: PublicKey) -> Result<Self, Error> {
+        let size = serialized_size(&(&value[..], &owner)).unwrap_or(u64::MAX);
+        if size > MAX_CHUNK_SIZE_IN_BYTES {
+            return Err(Error::ExceededSize);
+        }
+        Ok(Self::new(value, owner))
+    }
+
+    /// Creates a new instance of `PrivateChunk` holding `value` encrypted with `sym_key`
+    /// (ChaCha20-Poly1305, with a random nonce and the owner as associated data).
+    ///
+    /// The name is computed from the encrypted value and the owner, as for any chunk, so it
+    /// doesn't reveal the content. Use `decrypt` to recover the original value.
+    pub fn new_encrypted(
+        value: Vec<u8>,
+        owner: PublicKey,
+        sym_key: &[u8; 32],
+    ) -> Result<Self, Error> {
+        let mut nonce = [0; PRIVATE_CHUNK_NONCE_LEN];
+        rand::thread_rng().fill(&mut nonce);
+        let owner_bytes = owner.to_bytes();
+        let ciphertext = ChaCha20Poly1305::new(sym_key.into())
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &value,
+                    aad: &owner_bytes,
+                },
+            )
+            .map_err(|_| Error::InvalidOperation)?;
+
+        let mut encrypted = nonce.to_vec();
+        encrypted.extend(ciphertext);
+        Ok(Self::new(encrypted, owner))
+    }
+
+    /// Decrypts the value of a chunk created with `new_encrypted`.
+    /// Returns `Err(Error::FailedToDecrypt)` if `sym_key` is wrong or the value isn't encrypted.
+    pub fn decrypt(&self, sym_key: &[u8; 32]) -> Result<Vec<u8>, Error> {
+        if self.value.len() < PRIVATE_CHUNK_NONCE_LEN {
+            return Err(Error::FailedToDecrypt);
+        }
+        let (nonce, ciphertext) = self.value.split_at(PRIVATE_CHUNK_NONCE_LEN);
+        let owner_bytes = self.owner.to_bytes();
+        ChaCha20Poly1305::new(sym_key.into())
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &owner_bytes,
+                },
+            )
+            .map_err(|_| Error::FailedToDecrypt)
+    }
+
+    /// Returns the address a `PrivateChunk` with the given value and owner would have,
+    /// without requiring ownership of the value.
+    pub fn address_of(value: &[u8], owner: &PublicKey) -> Address {
+        Address::Private(XorName::from_content(&[value, &owner.to_bytes()]))
+    }
+
     /// Returns the value.
     pub fn value(&self) -> &Vec<u8> {
         &self.value
@@ -63,6 +138,22 @@ impl PrivateChunk {
         self.address.name()
     }
 
+    /// Returns a name that can be used to deduplicate identical chunks of the same owner,
+    /// keyed with `dedup_key`, a secret held by the owner (e.g. derived from their secret key).
+    ///
+    /// `name()` already differs between owners storing the same value, but it's computed from
+    /// the owner's public key, so anyone knowing the owner can confirm a guess of the content.
+    /// This name can't be computed, nor guesses confirmed, without `dedup_key`. The same owner
+    /// storing the same value with the same key always gets the same name.
+    pub fn dedup_name(&self, dedup_key: &[u8; 32]) -> XorName {
+        XorName::from_content(&[
+            PRIVATE_CHUNK_DEDUP_DOMAIN,
+            dedup_key,
+            &self.owner.to_bytes(),
+            &self.value,
+        ])
+    }
+
     /// Returns size of contained value.
     pub fn payload_size(&self) -> usize {
         self.value.len()
@@ -118,11 +209,27 @@ impl PublicChunk {
     /// Creates a new instance of `Chunk`.
     pub fn new(value: Vec<u8>) -> Self {
         Self {
-            address: Address::Public(XorName::from_content(&[&value])),
+            address: Self::address_of(&value),
             value,
         }
     }
 
+    /// Creates a new instance of `PublicChunk`, or returns `Err(Error::ExceededSize)` without
+    /// building it if its serialised size would exceed `MAX_CHUNK_SIZE_IN_BYTES`.
+    /// A chunk of exactly `MAX_CHUNK_SIZE_IN_BYTES` is accepted, one byte more is rejected.
+    pub fn new_checked(value: Vec<u8>) -> Result<Self, Error> {
+        if serialized_size(&value).unwrap_or(u64::MAX) > MAX_CHUNK_SIZE_IN_BYTES {
+            return Err(Error::ExceededSize);
+        }
+        Ok(Self::new(value))
+    }
+
+    /// Returns the address a `PublicChunk` with the given value would have,
+    /// without requiring ownership of the value.
+    pub fn address_of(value: &[u8]) -> Address {
+        Address::Public(XorName::from_content(&[value]))
+    }
+
     /// Returns the value.
     pub fn value(&self) -> &Vec<u8> {
         &self.value
@@ -204,7 +311,10 @@ impl Kind {
 }
 
 /// Address of an Chunk.
+///
+/// In human-readable formats such as JSON, it is serialised as its z-base-32 string.
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Debug)]
+#[serde(remote = "Self")]
 pub enum Address {
     /// Private namespace.
     Private(XorName),
@@ -257,6 +367,28 @@ impl Address {
     }
 }
 
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let encoded = self.encode_to_zbase32().map_err(S::Error::custom)?;
+            serializer.serialize_str(&encoded)
+        } else {
+            Address::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let encoded = String::deserialize(deserializer)?;
+            Address::decode_from_zbase32(encoded).map_err(D::Error::custom)
+        } else {
+            Address::deserialize(deserializer)
+        }
+    }
+}
+
 /// Object storing an Chunk variant.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Debug)]
 pub enum Chunk {
@@ -342,8 +474,10 @@ impl From<PublicChunk> for Chunk {
 
 #[cfg(test)]
 mod tests {
-    use super::{Address, PrivateChunk, PublicChunk, PublicKey, XorName};
-    use crate::{utils, Result};
+    use super::{
+        Address, Kind, PrivateChunk, PublicChunk, PublicKey, XorName, MAX_CHUNK_SIZE_IN_BYTES,
+    };
+    use crate::{utils, Error, Result};
     use hex::encode;
     use rand::{self, Rng, SeedableRng};
     use rand_xorshift::XorShiftRng;
@@ -370,6 +504,121 @@ mod tests {
         assert_ne!(ichunk2.name(), ichunk3.name());
     }
 
+    #[test]
+    fn dedup_name_is_per_owner() {
+        let value = b"Hello".to_vec();
+
+        let owner1 = PublicKey::Bls(SecretKey::random().public_key());
+        let owner2 = PublicKey::Bls(SecretKey::random().public_key());
+
+        let key1 = [1; 32];
+        let key2 = [2; 32];
+
+        let chunk1 = PrivateChunk::new(value.clone(), owner1);
+        let chunk1_again = PrivateChunk::new(value.clone(), owner1);
+        let chunk2 = PrivateChunk::new(value, owner2);
+
+        assert_eq!(chunk1.dedup_name(&key1), chunk1_again.dedup_name(&key1));
+        // the same key and content give different names for different owners
+        assert_ne!(chunk1.dedup_name(&key1), chunk2.dedup_name(&key1));
+        assert_ne!(&chunk1.dedup_name(&key1), chunk1.name());
+        // without the owner's key, the name can't be reproduced from the content
+        assert_ne!(chunk1.dedup_name(&key1), chunk1.dedup_name(&key2));
+    }
+
+    #[test]
+    fn address_of_borrowed_value() {
+        let buffer = b"some chunk value in a buffer".to_vec();
+        let owner = PublicKey::Bls(SecretKey::random().public_key());
+
+        assert_eq!(
+            &PublicChunk::address_of(&buffer[..]),
+            PublicChunk::new(buffer.clone()).address()
+        );
+        assert_eq!(
+            &PrivateChunk::address_of(&buffer[..], &owner),
+            PrivateChunk::new(buffer.clone(), owner).address()
+        );
+    }
+
+    #[test]
+    fn new_checked_size_limit() -> Result<()> {
+        let owner = PublicKey::Bls(SecretKey::random().public_key());
+
+        // the serialised size includes the length prefix of the value
+        let public_overhead = PublicChunk::new(vec![]).serialised_size();
+        let max_len = (MAX_CHUNK_SIZE_IN_BYTES - public_overhead) as usize;
+        let chunk = PublicChunk::new_checked(vec![0; max_len])?;
+        assert_eq!(chunk.serialised_size(), MAX_CHUNK_SIZE_IN_BYTES);
+        assert_eq!(chunk, PublicChunk::new(vec![0; max_len]));
+        assert_eq!(
+            PublicChunk::new_checked(vec![0; max_len + 1]),
+            Err(Error::ExceededSize)
+        );
+
+        let private_overhead = PrivateChunk::new(vec![], owner).serialised_size();
+        let max_len = (MAX_CHUNK_SIZE_IN_BYTES - private_overhead) as usize;
+        let chunk = PrivateChunk::new_checked(vec![0; max_len], owner)?;
+        assert_eq!(chunk.serialised_size(), MAX_CHUNK_SIZE_IN_BYTES);
+        assert_eq!(
+            PrivateChunk::new_checked(vec![0; max_len + 1], owner),
+            Err(Error::ExceededSize)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn json_serialisation() -> Result<()> {
+        let owner = PublicKey::Bls(SecretKey::random().public_key());
+        let chunk = PrivateChunk::new(b"value".to_vec(), owner);
+
+        let json = utils::to_json(chunk.address())?;
+        assert_eq!(
+            json,
+            format!("\"{}\"", chunk.address().encode_to_zbase32()?)
+        );
+        assert_eq!(utils::from_json::<Address>(&json)?, *chunk.address());
+
+        let json = utils::to_json(&chunk)?;
+        assert!(json.contains(&owner.encode_to_zbase32()?));
+        assert_eq!(utils::from_json::<PrivateChunk>(&json)?, chunk);
+
+        // bincode serialisation is unaffected
+        let address: Address = utils::deserialise(&utils::serialise(chunk.address())?)?;
+        assert_eq!(address, *chunk.address());
+        assert!(utils::from_json::<Address>("\"not z-base-32\"").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn encrypted_private_chunk() -> Result<()> {
+        let owner = PublicKey::Bls(SecretKey::random().public_key());
+        let value = b"private value".to_vec();
+        let sym_key = [1; 32];
+
+        let chunk = PrivateChunk::new_encrypted(value.clone(), owner, &sym_key)?;
+        assert_ne!(chunk.value(), &value);
+        assert_eq!(chunk.decrypt(&sym_key)?, value);
+        assert_eq!(chunk.decrypt(&[2; 32]), Err(Error::FailedToDecrypt));
+
+        // the name is derived from the encrypted value and the owner
+        assert_eq!(
+            chunk.address(),
+            &PrivateChunk::address_of(chunk.value(), &owner)
+        );
+        let parsed: PrivateChunk = utils::deserialise(&utils::serialise(&chunk)?)?;
+        assert_eq!(parsed.name(), chunk.name());
+        assert_eq!(parsed.decrypt(&sym_key)?, value);
+
+        // not an encrypted chunk
+        let plain = PrivateChunk::new(b"plain".to_vec(), owner);
+        assert_eq!(plain.decrypt(&sym_key), Err(Error::FailedToDecrypt));
+
+        Ok(())
+    }
+
     #[test]
     fn deterministic_test() {
         let value = "immutable chunk value".to_owned().into_bytes();
@@ -406,6 +655,23 @@ mod tests {
         XorShiftRng::seed_from_u64(seed)
     }
 
+    #[test]
+    fn chunk_address_accessors() {
+        let name = XorName::random();
+
+        let public = Address::Public(name);
+        assert_eq!(public.name(), &name);
+        assert_eq!(public.kind(), Kind::Pub);
+        assert!(public.is_public());
+        assert!(!public.is_private());
+
+        let private = Address::Private(name);
+        assert_eq!(private.name(), &name);
+        assert_eq!(private.kind(), Kind::Private);
+        assert!(private.is_private());
+        assert!(!private.is_public());
+    }
+
     #[test]
     fn zbase32_encode_decode_chunk_address() -> Result<()> {
         let name = XorName::random();
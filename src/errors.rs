@@ -8,7 +8,7 @@
 // Software.
 
 use super::register::Address;
-use crate::PublicKey;
+use crate::{PublicKey, Token};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
@@ -104,8 +104,185 @@ pub enum Error {
     /// The CRDT operation cannot be applied as it targets a different content address.
     #[error("The CRDT operation cannot be applied as it targets a different content address.")]
     CrdtWrongAddress(Address),
+    /// The balance is too low to apply a debit of the requested amount.
+    #[error("Insufficient balance to complete this operation: {available} available, {required} required")]
+    InsufficientBalance {
+        /// The current balance.
+        available: Token,
+        /// The amount that was requested.
+        required: Token,
+    },
+    /// Not enough distinct BLS key shares were provided to interpolate the group key.
+    #[error("Not enough distinct key shares to interpolate the group key")]
+    NotEnoughKeyShares,
+    /// Not enough BLS signature shares were provided to combine them into a signature.
+    #[error("Not enough signature shares to combine them into a signature")]
+    NotEnoughSignatureShares,
+    /// More than one BLS signature share was provided for the same index.
+    #[error("Duplicate signature share index: {0}")]
+    DuplicateShareIndex(usize),
+    /// Data could not be decrypted, e.g. with the wrong key.
+    #[error("Failed to decrypt the data")]
+    FailedToDecrypt,
+}
+
+impl Error {
+    /// Returns a stable numeric code for this error, so clients in other languages can
+    /// handle errors without depending on the serialised form or the message.
+    ///
+    /// Codes are allocated in ranges of 100 per area:
+    /// - `100..=199`: keys, signatures and encryption
+    /// - `200..=299`: access control
+    /// - `300..=399`: data and entries
+    /// - `400..=499`: CRDT operations
+    /// - `500..=599`: tokens and balances
+    /// - `600..=699`: serialisation and parsing
+    ///
+    /// Codes are never reassigned: a new variant gets the next free code in its area, so codes
+    /// don't shift when variants are added. The serialised form relies on the variant order,
+    /// so new variants must also only ever be appended to the enum.
+    pub fn code(&self) -> u16 {
+        match self {
+            // keys, signatures and encryption
+            Self::SigningKeyTypeMismatch => 100,
+            Self::InvalidSignature => 101,
+            Self::InvalidOwnerNotPublicKeySet => 102,
+            Self::NotEnoughKeyShares => 103,
+            Self::NotEnoughSignatureShares => 104,
+            Self::DuplicateShareIndex(_) => 105,
+            Self::FailedToDecrypt => 106,
+            // access control
+            Self::AccessDenied(_) => 200,
+            Self::PolicyNotSet => 201,
+            // data and entries
+            Self::NoSuchEntry => 300,
+            Self::NoSuchKey => 301,
+            Self::EntryExists(_) => 302,
+            Self::InvalidEntryActions(_) => 303,
+            Self::InvalidSuccessor(_) => 304,
+            Self::InvalidOperation => 305,
+            Self::ExceededSize => 306,
+            // CRDT operations
+            Self::OpNotCausallyReady => 400,
+            Self::CrdtMissingOpSignature => 401,
+            Self::CrdtUnexpectedState => 402,
+            Self::CrdtWrongAddress(_) => 403,
+            // tokens and balances
+            Self::LossOfPrecision => 500,
+            Self::ExcessiveValue => 501,
+            Self::NoSuchRecipient => 502,
+            Self::InsufficientBalance { .. } => 503,
+            // serialisation and parsing
+            Self::Serialisation(_) => 600,
+            Self::FailedToParse(_) => 601,
+        }
+    }
+
+    /// Returns the error with the given code, as returned by `Error::code`.
+    ///
+    /// Only variants which carry no payload can be rebuilt from their code; `None` is returned
+    /// for the others, as well as for unknown codes.
+    pub fn from_code(code: u16) -> Option<Self> {
+        let error = match code {
+            100 => Self::SigningKeyTypeMismatch,
+            101 => Self::InvalidSignature,
+            102 => Self::InvalidOwnerNotPublicKeySet,
+            103 => Self::NotEnoughKeyShares,
+            104 => Self::NotEnoughSignatureShares,
+            106 => Self::FailedToDecrypt,
+            201 => Self::PolicyNotSet,
+            300 => Self::NoSuchEntry,
+            301 => Self::NoSuchKey,
+            305 => Self::InvalidOperation,
+            306 => Self::ExceededSize,
+            400 => Self::OpNotCausallyReady,
+            401 => Self::CrdtMissingOpSignature,
+            402 => Self::CrdtUnexpectedState,
+            500 => Self::LossOfPrecision,
+            501 => Self::ExcessiveValue,
+            502 => Self::NoSuchRecipient,
+            _ => return None,
+        };
+        Some(error)
+    }
 }
 
 pub(crate) fn convert_bincode_error(err: bincode::Error) -> Error {
     Error::Serialisation(err.as_ref().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Keypair, RegisterAddress};
+    use std::collections::BTreeSet;
+    use xor_name::XorName;
+
+    #[test]
+    fn error_codes_are_stable_and_unique() {
+        let key = Keypair::new_ed25519(&mut rand::thread_rng()).public_key();
+        let address = RegisterAddress::Public {
+            name: XorName::random(),
+            tag: 0,
+        };
+        let codes = vec![
+            (Error::SigningKeyTypeMismatch, 100),
+            (Error::InvalidSignature, 101),
+            (Error::InvalidOwnerNotPublicKeySet, 102),
+            (Error::NotEnoughKeyShares, 103),
+            (Error::NotEnoughSignatureShares, 104),
+            (Error::DuplicateShareIndex(0), 105),
+            (Error::FailedToDecrypt, 106),
+            (Error::AccessDenied(key), 200),
+            (Error::PolicyNotSet, 201),
+            (Error::NoSuchEntry, 300),
+            (Error::NoSuchKey, 301),
+            (Error::EntryExists(0), 302),
+            (Error::InvalidEntryActions(BTreeMap::new()), 303),
+            (Error::InvalidSuccessor(0), 304),
+            (Error::InvalidOperation, 305),
+            (Error::ExceededSize, 306),
+            (Error::OpNotCausallyReady, 400),
+            (Error::CrdtMissingOpSignature, 401),
+            (Error::CrdtUnexpectedState, 402),
+            (Error::CrdtWrongAddress(address), 403),
+            (Error::LossOfPrecision, 500),
+            (Error::ExcessiveValue, 501),
+            (Error::NoSuchRecipient, 502),
+            (
+                Error::InsufficientBalance {
+                    available: Token::zero(),
+                    required: Token::zero(),
+                },
+                503,
+            ),
+            (Error::Serialisation(String::new()), 600),
+            (Error::FailedToParse(String::new()), 601),
+        ];
+
+        let mut seen = BTreeSet::new();
+        for (error, code) in codes {
+            assert_eq!(error.code(), code, "{:?}", error);
+            assert!(seen.insert(code));
+
+            // only the variants without payload round-trip through their code
+            match Error::from_code(code) {
+                Some(from_code) => assert_eq!(from_code, error),
+                None => assert!(matches!(
+                    error,
+                    Error::AccessDenied(_)
+                        | Error::EntryExists(_)
+                        | Error::InvalidEntryActions(_)
+                        | Error::InvalidSuccessor(_)
+                        | Error::CrdtWrongAddress(_)
+                        | Error::InsufficientBalance { .. }
+                        | Error::Serialisation(_)
+                        | Error::FailedToParse(_)
+                        | Error::DuplicateShareIndex(_)
+                )),
+            }
+        }
+        assert_eq!(Error::from_code(0), None);
+        assert_eq!(Error::from_code(199), None);
+    }
+}
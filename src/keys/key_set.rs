@@ -0,0 +1,84 @@
+// Copyright 2021 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// https://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+use super::PublicKey;
+use serde::{Deserialize, Serialize};
+use threshold_crypto::PublicKeySet;
+
+/// A BLS public key set: the group public key and the public key shares of its members.
+///
+/// Serialised as the compressed bytes of the key set's commitment.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct BlsKeySet(PublicKeySet);
+
+impl BlsKeySet {
+    /// Returns the threshold `t`: any `t + 1` signature shares can be combined into a
+    /// signature verifiable with the group key.
+    pub fn threshold(&self) -> usize {
+        self.0.threshold()
+    }
+
+    /// Returns the group `PublicKey::Bls`.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::Bls(self.0.public_key())
+    }
+
+    /// Returns the `PublicKey::BlsShare` of the member at `index`.
+    pub fn public_key_share(&self, index: usize) -> PublicKey {
+        PublicKey::BlsShare(self.0.public_key_share(index))
+    }
+
+    /// Returns the underlying `threshold_crypto::PublicKeySet`.
+    pub fn as_public_key_set(&self) -> &PublicKeySet {
+        &self.0
+    }
+}
+
+impl From<PublicKeySet> for BlsKeySet {
+    fn from(set: PublicKeySet) -> Self {
+        Self(set)
+    }
+}
+
+impl From<BlsKeySet> for PublicKeySet {
+    fn from(set: BlsKeySet) -> Self {
+        set.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{utils, Result};
+    use threshold_crypto::SecretKeySet;
+
+    #[test]
+    fn serialisation_round_trip() -> Result<()> {
+        // 3-of-4: a threshold of 2 needs 3 shares
+        let sk_set = SecretKeySet::random(2, &mut rand::thread_rng());
+        let key_set = BlsKeySet::from(sk_set.public_keys());
+        assert_eq!(key_set.threshold(), 2);
+
+        let parsed: BlsKeySet = utils::deserialise(&utils::serialise(&key_set)?)?;
+        assert_eq!(parsed, key_set);
+        assert_eq!(parsed.threshold(), 2);
+        assert_eq!(
+            parsed.public_key(),
+            PublicKey::Bls(sk_set.public_keys().public_key())
+        );
+        for index in 0..4 {
+            assert_eq!(
+                parsed.public_key_share(index),
+                PublicKey::BlsShare(sk_set.secret_key_share(index).public_key_share())
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -13,15 +13,19 @@
 //! `new` functions. A `PublicKey` can't be generated by itself; it must always be derived from a
 //! secret key.
 
-use crate::{Error, Result};
-use crate::{PublicKey, SecretKey, Signature, SignatureShare};
+use crate::{utils, Error, Result};
+use crate::{NodeIdentity, PublicKey, SecretKey, Signature, SignatureShare};
+
+use super::secret_key::bls_secret_share_ct_eq;
 
 use ed25519_dalek::Signer;
 use rand::{CryptoRng, Rng};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
+use subtle::ConstantTimeEq;
 use threshold_crypto::{self, serde_impl::SerdeSecret, PublicKeySet};
+use tiny_keccak::{Hasher, Sha3};
 
 #[derive(Clone, PartialEq, Eq)]
 /// Entity that owns the data or tokens.
@@ -63,7 +67,9 @@ impl OwnerType {
                     false
                 }
             }
-            ed @ Signature::Ed25519(_) => self.public_key().verify(ed, data).is_ok(),
+            sig @ Signature::Ed25519(_) | sig @ Signature::Secp256k1(_) => {
+                self.public_key().verify(sig, data).is_ok()
+            }
             Signature::BlsShare(share) => {
                 if let OwnerType::Multi(set) = self {
                     let pubkey_share = set.public_key_share(share.index);
@@ -135,15 +141,44 @@ impl Debug for Keypair {
     }
 }
 
+/// Domain separator of the KDF deriving Ed25519 keypairs from a seed.
+const ED25519_SEED_KDF_DOMAIN: &[u8] = b"sn_data_types::Keypair::new_ed25519_from_seed";
+/// Domain separator of the KDF deriving BLS keypairs from a seed.
+const BLS_SEED_KDF_DOMAIN: &[u8] = b"sn_data_types::Keypair::new_bls_from_seed";
+
+/// Domain separator of the KDF deriving child keypairs.
+const CHILD_KEYPAIR_KDF_DOMAIN: &[u8] = b"sn_data_types::Keypair::derive_child";
+
+/// Tag byte of an Ed25519 keypair in its `Keypair::to_bytes` encoding.
+const ED25519_KEYPAIR_TAG: u8 = 0;
+/// Tag byte of a BLS keypair share in its `Keypair::to_bytes` encoding.
+const BLS_SHARE_KEYPAIR_TAG: u8 = 1;
+
 // Need to manually implement this due to a missing impl in `Ed25519::Keypair`.
 impl PartialEq for Keypair {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Self::Ed25519(keypair), Self::Ed25519(other_keypair)) => {
-                // TODO: After const generics land, remove the `to_vec()` calls.
-                keypair.to_bytes().to_vec() == other_keypair.to_bytes().to_vec()
+                // Only the secret key is compared in constant time, borrowing its bytes so no
+                // copy of it is left on the stack.
+                keypair.public == other_keypair.public
+                    && bool::from(
+                        keypair
+                            .secret
+                            .as_bytes()
+                            .ct_eq(other_keypair.secret.as_bytes()),
+                    )
+            }
+            (Self::BlsShare(keypair), Self::BlsShare(other_keypair)) => {
+                // Only the secret share is compared in constant time, the rest is public.
+                keypair.index == other_keypair.index
+                    && keypair.public == other_keypair.public
+                    && keypair.public_key_set == other_keypair.public_key_set
+                    && bool::from(bls_secret_share_ct_eq(
+                        &keypair.secret,
+                        &other_keypair.secret,
+                    ))
             }
-            (Self::BlsShare(keypair), Self::BlsShare(other_keypair)) => keypair == other_keypair,
             _ => false,
         }
     }
@@ -159,6 +194,44 @@ impl Keypair {
         Self::Ed25519(Arc::new(keypair))
     }
 
+    /// Constructs an Ed25519 keypair deterministically from `seed`: the same seed always
+    /// yields the same keypair.
+    pub fn new_ed25519_from_seed(seed: &[u8; 32]) -> Result<Self> {
+        let secret_bytes = kdf(ED25519_SEED_KDF_DOMAIN, &[seed]);
+        let secret = ed25519_dalek::SecretKey::from_bytes(&secret_bytes).map_err(|e| {
+            Error::FailedToParse(format!("Couldn't derive Ed25519 secret key: {}", e))
+        })?;
+        Ok(Self::from(secret))
+    }
+
+    /// Constructs a BLS keypair deterministically from `seed`: the same seed always yields the
+    /// same keypair.
+    ///
+    /// As BLS keypairs are held as shares, this is the only share (index 0) of a key set with
+    /// a threshold of 0, i.e. its signature shares are valid signatures of the group key.
+    pub fn new_bls_from_seed(seed: &[u8; 32]) -> Result<Self> {
+        use threshold_crypto::{ff::PrimeField, poly::Poly, Fr, FrRepr, SecretKeySet};
+
+        let secret_bytes = kdf(BLS_SEED_KDF_DOMAIN, &[seed]);
+        let mut limbs = [0u64; 4];
+        for (limb, chunk) in limbs.iter_mut().zip(secret_bytes.chunks_exact(8)) {
+            let mut limb_bytes = [0; 8];
+            limb_bytes.copy_from_slice(chunk);
+            *limb = u64::from_le_bytes(limb_bytes);
+        }
+        // Clear the top bits so the value is below the field modulus.
+        limbs[3] &= u64::MAX >> 2;
+        let secret = Fr::from_repr(FrRepr(limbs))
+            .map_err(|e| Error::FailedToParse(format!("Couldn't derive BLS secret key: {}", e)))?;
+
+        let secret_key_set = SecretKeySet::from(Poly::constant(secret));
+        Ok(Self::new_bls_share(
+            0,
+            secret_key_set.secret_key_share(0),
+            secret_key_set.public_keys(),
+        ))
+    }
+
     /// Constructs a BLS keypair share.
     pub fn new_bls_share(
         index: usize,
@@ -181,6 +254,11 @@ impl Keypair {
         }
     }
 
+    /// Returns the public identity of this keypair, for use in membership sets.
+    pub fn identity(&self) -> NodeIdentity {
+        NodeIdentity(self.public_key())
+    }
+
     /// Returns the secret key associated with this keypair.
     pub fn secret_key(&self) -> Result<SecretKey> {
         match self {
@@ -197,6 +275,82 @@ impl Keypair {
         }
     }
 
+    /// Deterministically derives a child Ed25519 keypair for the given `app_id` from this
+    /// keypair's secret key, so each app gets its own identity which can always be recovered
+    /// from the root keypair, while exposing a child key doesn't expose its siblings or root.
+    ///
+    /// Only Ed25519 keypairs can be derived from; `Err(Error::InvalidOperation)` is returned
+    /// for BLS keypair shares.
+    pub fn derive_child(&self, app_id: &[u8]) -> Result<Self> {
+        let keypair = match self {
+            Self::Ed25519(keypair) => keypair,
+            Self::BlsShare(_) => return Err(Error::InvalidOperation),
+        };
+
+        let child_secret = kdf(
+            CHILD_KEYPAIR_KDF_DOMAIN,
+            &[keypair.secret.as_bytes(), app_id],
+        );
+
+        let secret = ed25519_dalek::SecretKey::from_bytes(&child_secret).map_err(|e| {
+            Error::FailedToParse(format!("Couldn't derive Ed25519 secret key: {}", e))
+        })?;
+        Ok(Self::from(secret))
+    }
+
+    /// Returns the keypair, including its secret, encoded for persistence: a tag byte for the
+    /// type of keypair, followed by the Ed25519 secret and public key bytes, or the serialised
+    /// BLS keypair share.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let (tag, key_bytes) = match self {
+            Self::Ed25519(keypair) => (ED25519_KEYPAIR_TAG, keypair.to_bytes().to_vec()),
+            Self::BlsShare(keypair) => (BLS_SHARE_KEYPAIR_TAG, utils::serialise(keypair)?),
+        };
+        let mut bytes = Vec::with_capacity(1 + key_bytes.len());
+        bytes.push(tag);
+        bytes.extend(key_bytes);
+        Ok(bytes)
+    }
+
+    /// Decodes a keypair encoded with `to_bytes`.
+    /// Returns `Err(Error::FailedToParse)` if the bytes are malformed or truncated.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.split_first() {
+            Some((&ED25519_KEYPAIR_TAG, key_bytes)) => {
+                let keypair = ed25519_dalek::Keypair::from_bytes(key_bytes).map_err(|e| {
+                    Error::FailedToParse(format!("Couldn't parse Ed25519 keypair: {}", e))
+                })?;
+                // Rederive the public key rather than trusting the stored one.
+                let public: ed25519_dalek::PublicKey = (&keypair.secret).into();
+                if public != keypair.public {
+                    return Err(Error::FailedToParse(
+                        "Ed25519 public key doesn't match the secret key".to_string(),
+                    ));
+                }
+                Ok(Self::Ed25519(Arc::new(keypair)))
+            }
+            Some((&BLS_SHARE_KEYPAIR_TAG, key_bytes)) => {
+                let keypair: BlsKeypairShare = utils::deserialise(key_bytes).map_err(|_| {
+                    Error::FailedToParse("Couldn't parse BLS keypair share".to_string())
+                })?;
+                // Check the stored public key share against both the secret and the key set.
+                if keypair.secret.inner().public_key_share() != keypair.public
+                    || keypair.public_key_set.public_key_share(keypair.index) != keypair.public
+                {
+                    return Err(Error::FailedToParse(
+                        "BLS public key share doesn't match the secret key share".to_string(),
+                    ));
+                }
+                Ok(Self::BlsShare(Arc::new(keypair)))
+            }
+            Some((tag, _)) => Err(Error::FailedToParse(format!(
+                "Unknown keypair type tag: {}",
+                tag
+            ))),
+            None => Err(Error::FailedToParse("Empty keypair bytes".to_string())),
+        }
+    }
+
     /// Signs with the underlying keypair.
     pub fn sign(&self, data: &[u8]) -> Signature {
         match self {
@@ -209,6 +363,18 @@ impl Keypair {
     }
 }
 
+// Derives 32 bytes of key material from `inputs`, hashed in order under the KDF `domain`.
+fn kdf(domain: &[u8], inputs: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha3::v256();
+    let mut output = [0; 32];
+    hasher.update(domain);
+    for input in inputs {
+        hasher.update(input);
+    }
+    hasher.finalize(&mut output);
+    output
+}
+
 impl From<ed25519_dalek::SecretKey> for Keypair {
     fn from(secret: ed25519_dalek::SecretKey) -> Self {
         let keypair = ed25519_dalek::Keypair {
@@ -278,4 +444,133 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn bls_share_keypair_eq() {
+        let sk_set = threshold_crypto::SecretKeySet::random(1, &mut rand::thread_rng());
+        let keypair = |index| {
+            Keypair::new_bls_share(index, sk_set.secret_key_share(index), sk_set.public_keys())
+        };
+
+        assert_eq!(keypair(0), keypair(0));
+        assert_ne!(keypair(0), keypair(1));
+    }
+
+    #[test]
+    fn keypair_to_from_bytes() -> Result<()> {
+        for keypair in gen_keypairs() {
+            let bytes = keypair.to_bytes()?;
+            let decoded = Keypair::from_bytes(&bytes)?;
+            assert_eq!(decoded, keypair);
+            assert_eq!(decoded.public_key(), keypair.public_key());
+
+            // truncated bytes
+            for len in &[0, 1, bytes.len() - 1] {
+                assert!(matches!(
+                    Keypair::from_bytes(&bytes[..*len]),
+                    Err(Error::FailedToParse(_))
+                ));
+            }
+        }
+
+        // unknown tag
+        assert!(matches!(
+            Keypair::from_bytes(&[2; 65]),
+            Err(Error::FailedToParse(_))
+        ));
+
+        // a public key which doesn't belong to the secret key
+        let mut bytes = Keypair::new_ed25519(&mut rand::thread_rng()).to_bytes()?;
+        let other = Keypair::new_ed25519(&mut rand::thread_rng()).to_bytes()?;
+        bytes[33..].copy_from_slice(&other[33..]);
+        assert!(matches!(
+            Keypair::from_bytes(&bytes),
+            Err(Error::FailedToParse(_))
+        ));
+
+        // BLS shares whose public key share doesn't match the secret or the key set
+        let sk_set = threshold_crypto::SecretKeySet::random(1, &mut rand::thread_rng());
+        let mismatched = [
+            BlsKeypairShare {
+                index: 0,
+                secret: SerdeSecret(sk_set.secret_key_share(0)),
+                public: sk_set.public_keys().public_key_share(1),
+                public_key_set: sk_set.public_keys(),
+            },
+            BlsKeypairShare {
+                index: 1,
+                secret: SerdeSecret(sk_set.secret_key_share(0)),
+                public: sk_set.secret_key_share(0).public_key_share(),
+                public_key_set: sk_set.public_keys(),
+            },
+        ];
+        for keypair in mismatched.iter().cloned() {
+            let bytes = Keypair::BlsShare(Arc::new(keypair)).to_bytes()?;
+            assert!(matches!(
+                Keypair::from_bytes(&bytes),
+                Err(Error::FailedToParse(_))
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn keypair_from_seed() -> Result<()> {
+        let seed = [1; 32];
+        let other_seed = [2; 32];
+
+        let ed25519 = Keypair::new_ed25519_from_seed(&seed)?;
+        assert_eq!(ed25519, Keypair::new_ed25519_from_seed(&seed)?);
+        assert_ne!(
+            ed25519.public_key(),
+            Keypair::new_ed25519_from_seed(&other_seed)?.public_key()
+        );
+
+        let bls = Keypair::new_bls_from_seed(&seed)?;
+        assert_eq!(bls, Keypair::new_bls_from_seed(&seed)?);
+        assert_eq!(
+            bls.public_key(),
+            Keypair::new_bls_from_seed(&seed)?.public_key()
+        );
+        assert_ne!(
+            bls.public_key(),
+            Keypair::new_bls_from_seed(&other_seed)?.public_key()
+        );
+
+        // the same seed gives unrelated keys for different schemes
+        assert_ne!(
+            ed25519.public_key().to_bytes()[..],
+            bls.public_key().to_bytes()[..32]
+        );
+
+        let signature = bls.sign(b"data");
+        bls.public_key().verify(&signature, b"data")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn derive_child() -> Result<()> {
+        let keypairs = gen_keypairs();
+        let root = &keypairs[0];
+
+        let child = root.derive_child(b"app1")?;
+        assert_eq!(child, root.derive_child(b"app1")?);
+        assert_ne!(child.public_key(), root.public_key());
+        assert_ne!(child.public_key(), root.derive_child(b"app2")?.public_key());
+        assert_ne!(
+            child.public_key(),
+            Keypair::new_ed25519(&mut rand::thread_rng())
+                .derive_child(b"app1")?
+                .public_key()
+        );
+
+        assert_eq!(
+            keypairs[1].derive_child(b"app1"),
+            Err(Error::InvalidOperation)
+        );
+
+        Ok(())
+    }
 }
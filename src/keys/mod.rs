@@ -13,13 +13,17 @@
 //! `new` functions. A `PublicKey` can't be generated by itself; it must always be derived from a
 //! secret key.
 
+mod key_set;
 mod keypair;
 mod node_keypairs;
 mod public_key;
+mod secp256k1_serde;
 mod secret_key;
 mod signature;
+pub mod sizes;
 
 pub use self::signature::*;
+pub use key_set::*;
 pub use keypair::*;
 pub use node_keypairs::*;
 pub use public_key::*;
@@ -16,19 +16,30 @@
 use crate::{utils, Error, Result};
 use crate::{Keypair, Signature};
 
-use serde::{Deserialize, Serialize};
+use super::sizes;
+
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use signature::Verifier;
 use std::{
     cmp::Ordering,
+    collections::BTreeMap,
     convert::TryInto,
     fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex},
     hash::{Hash, Hasher},
 };
+use tiny_keccak::{Hasher as _, Sha3};
 // use threshold_crypto::{self};
 use xor_name::{XorName, XOR_NAME_LEN};
 
+/// Multihash code of the identity "hash", i.e. the data itself.
+const MULTIHASH_IDENTITY_CODE: u8 = 0x00;
+
 /// Wrapper for different public key types.
+///
+/// In human-readable formats such as JSON, it is serialised as its z-base-32 string.
 #[derive(Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(remote = "Self")]
 pub enum PublicKey {
     /// Ed25519 public key.
     Ed25519(ed25519_dalek::PublicKey),
@@ -36,6 +47,35 @@ pub enum PublicKey {
     Bls(threshold_crypto::PublicKey),
     /// BLS public key share.
     BlsShare(threshold_crypto::PublicKeyShare),
+    /// Secp256k1 (ECDSA) public key.
+    Secp256k1(#[serde(with = "super::secp256k1_serde::public_key")] k256::ecdsa::VerifyingKey),
+}
+
+/// The signature scheme of a `PublicKey`, e.g. for logging and metrics.
+///
+/// Displayed as a lowercase tag, e.g. "bls".
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum KeyScheme {
+    /// Ed25519.
+    Ed25519,
+    /// BLS.
+    Bls,
+    /// BLS key share.
+    BlsShare,
+    /// Secp256k1 (ECDSA).
+    Secp256k1,
+}
+
+impl Display for KeyScheme {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let tag = match self {
+            Self::Ed25519 => "ed25519",
+            Self::Bls => "bls",
+            Self::BlsShare => "bls_share",
+            Self::Secp256k1 => "secp256k1",
+        };
+        write!(formatter, "{}", tag)
+    }
 }
 
 impl PublicKey {
@@ -88,12 +128,110 @@ impl PublicKey {
         Ok(Self::from(pk))
     }
 
+    /// Construct a secp256k1 public key from
+    /// a hex-encoded string of its SEC1 encoding
+    /// (compressed or uncompressed).
+    pub fn secp256k1_from_hex(hex: &str) -> Result<Self> {
+        let bytes = hex::decode(hex).map_err(|e| {
+            Error::FailedToParse(format!(
+                "Couldn't parse secp256k1 public key bytes from hex: {}",
+                e
+            ))
+        })?;
+        let pk = k256::ecdsa::VerifyingKey::from_sec1_bytes(&bytes).map_err(|e| {
+            Error::FailedToParse(format!(
+                "Couldn't parse secp256k1 public key from SEC1 bytes: {}",
+                e
+            ))
+        })?;
+        Ok(Self::from(pk))
+    }
+
+    /// Construct a public key from its raw bytes, inferring its type from their length:
+    /// - 32 bytes: Ed25519 public key
+    /// - 48 bytes: BLS public key (a BLS public key share can't be told apart from a BLS
+    ///   public key by its bytes, so it is parsed as a `PublicKey::Bls`)
+    /// - 33 or 65 bytes: secp256k1 public key, SEC1-encoded (compressed or uncompressed)
+    ///
+    /// Returns `Err(Error::FailedToParse)` for any other length or for invalid keys.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        match bytes.len() {
+            sizes::ED25519_PUBLIC_LEN => ed25519_dalek::PublicKey::from_bytes(bytes)
+                .map(Self::from)
+                .map_err(|e| {
+                    Error::FailedToParse(format!(
+                        "Couldn't parse ed25519 public key from bytes: {}",
+                        e
+                    ))
+                }),
+            sizes::BLS_PUBLIC_LEN => {
+                let mut bytes_fixed_len = [0; sizes::BLS_PUBLIC_LEN];
+                bytes_fixed_len.copy_from_slice(bytes);
+                threshold_crypto::PublicKey::from_bytes(bytes_fixed_len)
+                    .map(Self::from)
+                    .map_err(|e| {
+                        Error::FailedToParse(format!(
+                            "Couldn't parse BLS public key from bytes: {}",
+                            e
+                        ))
+                    })
+            }
+            sizes::SECP256K1_PUBLIC_LEN | sizes::SECP256K1_PUBLIC_UNCOMPRESSED_LEN => {
+                k256::ecdsa::VerifyingKey::from_sec1_bytes(bytes)
+                    .map(Self::from)
+                    .map_err(|e| {
+                        Error::FailedToParse(format!(
+                            "Couldn't parse secp256k1 public key from SEC1 bytes: {}",
+                            e
+                        ))
+                    })
+            }
+            len => Err(Error::FailedToParse(format!(
+                "Couldn't infer the public key type from its length: {} bytes",
+                len
+            ))),
+        }
+    }
+
     /// Returns the bytes of the underlying public key
     pub fn to_bytes(&self) -> Vec<u8> {
         match self {
             PublicKey::Ed25519(pub_key) => pub_key.to_bytes().into(),
             PublicKey::Bls(pub_key) => pub_key.to_bytes().into(),
             PublicKey::BlsShare(pub_key) => pub_key.to_bytes().into(),
+            PublicKey::Secp256k1(pub_key) => pub_key.to_encoded_point(true).as_bytes().into(),
+        }
+    }
+
+    /// Returns a short fingerprint of the key, e.g. "ed25519:1a2b3c4d5e6f7a8b", for correlating
+    /// log lines and displaying in UIs.
+    ///
+    /// It is made of the scheme and the first 8 bytes of the SHA3-256 hash of the full key, so
+    /// it is stable across runs, and keys sharing a prefix still have different fingerprints.
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha3::v256();
+        let mut output = [0; 32];
+        hasher.update(&self.to_bytes());
+        hasher.finalize(&mut output);
+        format!("{}:{}", self.scheme(), hex::encode(&output[..8]))
+    }
+
+    /// Returns the secp256k1 key, if applicable.
+    pub fn secp256k1(&self) -> Option<k256::ecdsa::VerifyingKey> {
+        if let Self::Secp256k1(key) = self {
+            Some(*key)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the signature scheme of the key.
+    pub fn scheme(&self) -> KeyScheme {
+        match self {
+            Self::Ed25519(_) => KeyScheme::Ed25519,
+            Self::Bls(_) => KeyScheme::Bls,
+            Self::BlsShare(_) => KeyScheme::BlsShare,
+            Self::Secp256k1(_) => KeyScheme::Secp256k1,
         }
     }
 
@@ -133,6 +271,9 @@ impl PublicKey {
             }
             (Self::Bls(pub_key), Signature::Bls(sig)) => pub_key.verify(sig, data),
             (Self::BlsShare(pub_key), Signature::BlsShare(sig)) => pub_key.verify(&sig.share, data),
+            (Self::Secp256k1(pub_key), Signature::Secp256k1(sig)) => {
+                pub_key.verify(data.as_ref(), sig).is_ok()
+            }
             _ => return Err(Error::SigningKeyTypeMismatch),
         };
         if is_valid {
@@ -142,6 +283,47 @@ impl PublicKey {
         }
     }
 
+    /// Verifies a batch of signatures, returning `Ok(())` if all of them are valid (or the batch
+    /// is empty), and `Err(Error::InvalidSignature)` if any of them isn't, including when a
+    /// signature's type doesn't match its key's.
+    ///
+    /// Ed25519 items are checked together with `ed25519_dalek::verify_batch`, while the other
+    /// items are checked one by one. All items are checked even after a failure, so the work
+    /// done doesn't depend on which item is invalid.
+    ///
+    /// Note that batch verification of Ed25519 signatures can accept some adversarially crafted
+    /// signatures which `verify` rejects (and vice versa), so nodes which must agree on whether
+    /// a signature is valid should all use the same one of the two.
+    pub fn verify_batch(items: &[(PublicKey, Signature, &[u8])]) -> Result<()> {
+        let mut ed25519_messages = Vec::new();
+        let mut ed25519_signatures = Vec::new();
+        let mut ed25519_keys = Vec::new();
+        let mut all_valid = true;
+
+        for (key, signature, data) in items {
+            match (key, signature) {
+                (Self::Ed25519(pub_key), Signature::Ed25519(sig)) => {
+                    ed25519_messages.push(*data);
+                    ed25519_signatures.push(*sig);
+                    ed25519_keys.push(*pub_key);
+                }
+                _ => all_valid &= key.verify(signature, data).is_ok(),
+            }
+        }
+
+        if !ed25519_keys.is_empty() {
+            all_valid &=
+                ed25519_dalek::verify_batch(&ed25519_messages, &ed25519_signatures, &ed25519_keys)
+                    .is_ok();
+        }
+
+        if all_valid {
+            Ok(())
+        } else {
+            Err(Error::InvalidSignature)
+        }
+    }
+
     /// Returns the `PublicKey` serialised and encoded in z-base-32.
     pub fn encode_to_zbase32(&self) -> Result<String> {
         utils::encode(&self)
@@ -151,6 +333,143 @@ impl PublicKey {
     pub fn decode_from_zbase32<I: AsRef<str>>(encoded: I) -> Result<Self> {
         utils::decode(encoded)
     }
+
+    /// Returns the key as a self-describing identity multihash: the identity code, the length
+    /// of the key bytes as a varint, then the key bytes (as returned by `to_bytes`).
+    pub fn to_multihash(&self) -> Vec<u8> {
+        let bytes = self.to_bytes();
+        let mut multihash = Vec::with_capacity(2 + bytes.len());
+        multihash.push(MULTIHASH_IDENTITY_CODE);
+        // Key bytes are shorter than 128 bytes, so their length is a single-byte varint.
+        multihash.push(bytes.len() as u8);
+        multihash.extend(bytes);
+        multihash
+    }
+
+    /// Parses a key from an identity multihash, as returned by `to_multihash`.
+    ///
+    /// As with `from_bytes`, a BLS public key share is parsed as a `PublicKey::Bls`.
+    /// Returns `Err(Error::FailedToParse)` for other hash codes, or if the multihash is
+    /// truncated or its length doesn't match.
+    pub fn from_multihash(multihash: &[u8]) -> Result<Self> {
+        match multihash {
+            [MULTIHASH_IDENTITY_CODE, len, bytes @ ..] if *len < 0x80 => {
+                if bytes.len() != *len as usize {
+                    return Err(Error::FailedToParse(format!(
+                        "Multihash length {} doesn't match its {} bytes",
+                        len,
+                        bytes.len()
+                    )));
+                }
+                Self::from_bytes(bytes)
+            }
+            [MULTIHASH_IDENTITY_CODE, ..] => Err(Error::FailedToParse(
+                "Truncated or oversized multihash".to_string(),
+            )),
+            [code, ..] => Err(Error::FailedToParse(format!(
+                "Unsupported multihash code: {:#x}",
+                code
+            ))),
+            [] => Err(Error::FailedToParse("Empty multihash".to_string())),
+        }
+    }
+}
+
+/// Returns the group `PublicKey::Bls` of the given `PublicKeySet`, along with the
+/// `PublicKey::BlsShare` of each of the members at the given `indices`.
+pub fn from_public_key_set(
+    set: &threshold_crypto::PublicKeySet,
+    indices: &[usize],
+) -> (PublicKey, Vec<PublicKey>) {
+    let shares = indices
+        .iter()
+        .map(|index| PublicKey::BlsShare(set.public_key_share(*index)))
+        .collect();
+    (PublicKey::Bls(set.public_key()), shares)
+}
+
+/// Returns the group `PublicKey::Bls` of a key set with the given `threshold`, interpolated from
+/// the `PublicKey::BlsShare`s of its members and their indices, without requiring the
+/// `PublicKeySet`.
+///
+/// At least `threshold + 1` shares with distinct indices are needed, otherwise
+/// `Err(Error::NotEnoughKeyShares)` is returned. Returns `Err(Error::InvalidOperation)` if any of
+/// the keys is not a `PublicKey::BlsShare`.
+pub fn interpolate_group_key(shares: &[(usize, PublicKey)], threshold: usize) -> Result<PublicKey> {
+    use threshold_crypto::{
+        ff::Field,
+        group::{CurveAffine, CurveProjective, EncodedPoint},
+        Fr, G1Affine, IntoFr, G1,
+    };
+
+    let mut samples = BTreeMap::new();
+    for (index, key) in shares {
+        let share = key.bls_share().ok_or(Error::InvalidOperation)?;
+        let _ = samples.insert(*index, share);
+    }
+    if samples.len() <= threshold {
+        return Err(Error::NotEnoughKeyShares);
+    }
+
+    // The share at index `i` is the value of the key set's polynomial at `i + 1`,
+    // and the group key its value at `0`.
+    let samples = samples
+        .into_iter()
+        .take(threshold + 1)
+        .map(|(index, share)| {
+            let mut x = Fr::one();
+            x.add_assign(&index.into_fr());
+            let mut compressed: <G1Affine as CurveAffine>::Compressed = EncodedPoint::empty();
+            compressed.as_mut().copy_from_slice(&share.to_bytes());
+            let point = compressed
+                .into_affine()
+                .map_err(|_| Error::FailedToParse("Invalid BLS public key share".to_string()))?;
+            Ok((x, point))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut group_key = G1::zero();
+    for (x, point) in &samples {
+        // The Lagrange basis polynomial for `x`, evaluated at `0`.
+        let mut numerator = Fr::one();
+        let mut denominator = Fr::one();
+        for (other_x, _) in samples.iter().filter(|(other_x, _)| other_x != x) {
+            numerator.mul_assign(other_x);
+            let mut diff = *other_x;
+            diff.sub_assign(x);
+            denominator.mul_assign(&diff);
+        }
+        numerator.mul_assign(&denominator.inverse().ok_or(Error::NotEnoughKeyShares)?);
+        group_key.add_assign(&point.mul(numerator));
+    }
+
+    let mut bytes = [0; threshold_crypto::PK_SIZE];
+    bytes.copy_from_slice(group_key.into_affine().into_compressed().as_ref());
+    let group_key = threshold_crypto::PublicKey::from_bytes(bytes)
+        .map_err(|_| Error::FailedToParse("Invalid interpolated BLS public key".to_string()))?;
+    Ok(PublicKey::Bls(group_key))
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let encoded = self.encode_to_zbase32().map_err(S::Error::custom)?;
+            serializer.serialize_str(&encoded)
+        } else {
+            PublicKey::serialize(self, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let encoded = String::deserialize(deserializer)?;
+            PublicKey::decode_from_zbase32(encoded).map_err(D::Error::custom)
+        } else {
+            PublicKey::deserialize(deserializer)
+        }
+    }
 }
 
 #[allow(clippy::derive_hash_xor_eq)]
@@ -174,6 +493,56 @@ impl PartialOrd for PublicKey {
     }
 }
 
+/// The public identity of a node, for use as a key in membership sets and maps.
+///
+/// Unlike `PublicKey`, which hashes its serialised form, `NodeIdentity` hashes the canonical
+/// key bytes directly, and it never holds any secret material.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NodeIdentity(pub PublicKey);
+
+impl NodeIdentity {
+    /// Returns the public key of this identity.
+    pub fn public_key(&self) -> PublicKey {
+        self.0
+    }
+}
+
+impl Hash for NodeIdentity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The scheme is hashed too, as a BLS key and a BLS key share have the same length.
+        match self.0 {
+            PublicKey::Ed25519(pub_key) => {
+                0u8.hash(state);
+                pub_key.as_bytes().hash(state)
+            }
+            PublicKey::Bls(pub_key) => {
+                1u8.hash(state);
+                pub_key.to_bytes().hash(state)
+            }
+            PublicKey::BlsShare(pub_key) => {
+                2u8.hash(state);
+                pub_key.to_bytes().hash(state)
+            }
+            PublicKey::Secp256k1(pub_key) => {
+                3u8.hash(state);
+                pub_key.to_encoded_point(true).as_bytes().hash(state)
+            }
+        }
+    }
+}
+
+impl From<PublicKey> for NodeIdentity {
+    fn from(public_key: PublicKey) -> Self {
+        Self(public_key)
+    }
+}
+
+impl Display for NodeIdentity {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, formatter)
+    }
+}
+
 impl From<PublicKey> for XorName {
     fn from(public_key: PublicKey) -> Self {
         let bytes = match public_key {
@@ -182,6 +551,14 @@ impl From<PublicKey> for XorName {
             }
             PublicKey::Bls(pub_key) => pub_key.to_bytes(),
             PublicKey::BlsShare(pub_key) => pub_key.to_bytes(),
+            PublicKey::Secp256k1(pub_key) => {
+                // Keep the SEC1 tag byte, as it holds the parity telling `P` and `-P` apart.
+                let mut xor_name = XorName::random();
+                xor_name
+                    .0
+                    .clone_from_slice(&pub_key.to_encoded_point(true).as_bytes()[..XOR_NAME_LEN]);
+                return xor_name;
+            }
         };
         let mut xor_name = XorName::random();
         xor_name.0.clone_from_slice(&bytes[..XOR_NAME_LEN]);
@@ -207,6 +584,12 @@ impl From<threshold_crypto::PublicKeyShare> for PublicKey {
     }
 }
 
+impl From<k256::ecdsa::VerifyingKey> for PublicKey {
+    fn from(public_key: k256::ecdsa::VerifyingKey) -> Self {
+        Self::Secp256k1(public_key)
+    }
+}
+
 impl From<&Keypair> for PublicKey {
     fn from(keypair: &Keypair) -> Self {
         keypair.public_key()
@@ -234,6 +617,11 @@ impl Debug for PublicKey {
                 "BlsShare({:<8})",
                 hex::encode(&pub_key.to_bytes()[..XOR_NAME_LEN])
             ),
+            Self::Secp256k1(pub_key) => write!(
+                formatter,
+                "Secp256k1({:<8})",
+                hex::encode(pub_key.to_encoded_point(true).as_bytes())
+            ),
         }
     }
 }
@@ -266,7 +654,7 @@ pub(crate) mod tests {
     use crate::utils;
     use threshold_crypto::{self};
 
-    fn gen_keypairs() -> Vec<Keypair> {
+    pub(crate) fn gen_keypairs() -> Vec<Keypair> {
         let mut rng = rand::thread_rng();
         let bls_secret_key = threshold_crypto::SecretKeySet::random(1, &mut rng);
         vec![
@@ -283,6 +671,82 @@ pub(crate) mod tests {
         gen_keypairs().iter().map(PublicKey::from).collect()
     }
 
+    // k256 can't generate keys with our version of rand, so a fixed secret is used.
+    pub(crate) fn gen_secp256k1_key() -> k256::ecdsa::SigningKey {
+        k256::ecdsa::SigningKey::from_bytes(&[7; 32]).expect("valid secp256k1 secret key")
+    }
+
+    #[test]
+    fn scheme() {
+        let mut rng = rand::thread_rng();
+        let bls_secret_key = threshold_crypto::SecretKeySet::random(1, &mut rng);
+        let secp256k1_key = gen_secp256k1_key().verifying_key();
+        let keys = [
+            (
+                Keypair::new_ed25519(&mut rng).public_key(),
+                KeyScheme::Ed25519,
+                "ed25519",
+            ),
+            (
+                PublicKey::Bls(bls_secret_key.public_keys().public_key()),
+                KeyScheme::Bls,
+                "bls",
+            ),
+            (
+                PublicKey::BlsShare(bls_secret_key.public_keys().public_key_share(0)),
+                KeyScheme::BlsShare,
+                "bls_share",
+            ),
+            (
+                PublicKey::from(secp256k1_key),
+                KeyScheme::Secp256k1,
+                "secp256k1",
+            ),
+        ];
+
+        for (key, scheme, tag) in keys.iter() {
+            assert_eq!(key.scheme(), *scheme);
+            assert_eq!(
+                format!("scheme={}", key.scheme()),
+                format!("scheme={}", tag)
+            );
+        }
+    }
+
+    #[test]
+    fn multihash() -> Result<()> {
+        let secp256k1_key = gen_secp256k1_key().verifying_key();
+        let keys = [
+            Keypair::new_ed25519(&mut rand::thread_rng()).public_key(),
+            PublicKey::Bls(threshold_crypto::SecretKey::random().public_key()),
+            PublicKey::from(secp256k1_key),
+        ];
+
+        for key in keys.iter() {
+            let multihash = key.to_multihash();
+            assert_eq!(multihash[0], 0x00);
+            assert_eq!(multihash[1] as usize, key.to_bytes().len());
+            assert_eq!(&PublicKey::from_multihash(&multihash)?, key);
+
+            for len in 0..multihash.len() {
+                assert!(matches!(
+                    PublicKey::from_multihash(&multihash[..len]),
+                    Err(Error::FailedToParse(_))
+                ));
+            }
+        }
+
+        // sha2-256 isn't supported, as a key can't be recovered from its hash
+        let mut sha2 = keys[0].to_multihash();
+        sha2[0] = 0x12;
+        assert!(matches!(
+            PublicKey::from_multihash(&sha2),
+            Err(Error::FailedToParse(_))
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn zbase32_encode_decode_public_key() -> Result<()> {
         let keys = gen_keys();
@@ -297,6 +761,63 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[test]
+    fn fingerprint() {
+        let keys = gen_keys();
+        let fingerprints: Vec<_> = keys.iter().map(PublicKey::fingerprint).collect();
+
+        assert!(fingerprints[0].starts_with("ed25519:"));
+        assert!(fingerprints[1].starts_with("bls_share:"));
+        assert_ne!(fingerprints[0], fingerprints[1]);
+        assert_ne!(gen_keys()[0].fingerprint(), fingerprints[0]);
+        for (key, fingerprint) in keys.iter().zip(&fingerprints) {
+            assert_eq!(key.fingerprint(), *fingerprint);
+            assert_eq!(fingerprint.split(':').nth(1).map(str::len), Some(16));
+        }
+    }
+
+    #[test]
+    fn node_identity_set() {
+        use std::collections::HashSet;
+
+        let keypairs = gen_keypairs();
+        let mut identities = HashSet::new();
+        for keypair in &keypairs {
+            assert!(identities.insert(keypair.identity()));
+            assert_eq!(keypair.identity().public_key(), keypair.public_key());
+        }
+        for keypair in &keypairs {
+            assert!(!identities.insert(NodeIdentity::from(keypair.public_key())));
+        }
+        assert_eq!(identities.len(), keypairs.len());
+    }
+
+    #[test]
+    fn public_keys_from_public_key_set() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let secret_key_set = threshold_crypto::SecretKeySet::random(1, &mut rng);
+        let data = b"hello";
+
+        let indices = [0, 2];
+        let public_key_set = secret_key_set.public_keys();
+        let (group_key, share_keys) = from_public_key_set(&public_key_set, &indices);
+        assert_eq!(share_keys.len(), indices.len());
+
+        let mut sig_shares = Vec::new();
+        for (index, share_key) in indices.iter().zip(share_keys) {
+            let sig_share = secret_key_set.secret_key_share(*index).sign(data);
+            share_key.verify(&Signature::from((*index, sig_share.clone())), data)?;
+            sig_shares.push((*index, sig_share));
+        }
+
+        let group_sig = public_key_set
+            .combine_signatures(sig_shares.iter().map(|(index, share)| (*index, share)))
+            .map_err(|_| Error::InvalidSignature)?;
+        group_key.verify(&Signature::Bls(group_sig), data)?;
+
+        Ok(())
+    }
+
     // Test serialising and deserialising public keys.
     #[test]
     fn serialisation_public_key() -> Result<()> {
@@ -311,4 +832,143 @@ pub(crate) mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn secp256k1_keys() -> Result<()> {
+        use signature::Signer;
+
+        let signing_key = gen_secp256k1_key();
+        let key = PublicKey::from(signing_key.verifying_key());
+        let data = b"hello";
+        let sig: k256::ecdsa::Signature = signing_key.sign(data);
+        let sig = Signature::from(sig);
+
+        assert_eq!(key.to_bytes().len(), sizes::SECP256K1_PUBLIC_LEN);
+        assert_eq!(PublicKey::secp256k1_from_hex(&format!("{:x}", key))?, key);
+        assert!(PublicKey::secp256k1_from_hex("00").is_err());
+
+        key.verify(&sig, data)?;
+        assert_eq!(key.verify(&sig, b"goodbye"), Err(Error::InvalidSignature));
+        let ed25519_sig = Keypair::new_ed25519(&mut rand::thread_rng()).sign(data);
+        assert_eq!(
+            key.verify(&ed25519_sig, data),
+            Err(Error::SigningKeyTypeMismatch)
+        );
+
+        let decoded: PublicKey = utils::deserialise(&utils::serialise(&key)?)?;
+        assert_eq!(decoded, key);
+        let decoded: Signature = utils::deserialise(&utils::serialise(&sig)?)?;
+        assert_eq!(decoded, sig);
+        assert_eq!(XorName::from(key).0[..], key.to_bytes()[..XOR_NAME_LEN]);
+
+        // the negated key has the same x-coordinate and only differs in its parity
+        let mut negated = key.to_bytes();
+        negated[0] ^= 1;
+        let negated = PublicKey::from_bytes(&negated)?;
+        assert_ne!(negated, key);
+        assert_ne!(XorName::from(negated), XorName::from(key));
+
+        Ok(())
+    }
+
+    #[test]
+    fn verify_batch() -> Result<()> {
+        // two ed25519 keypairs, then a BLS share one
+        let mut keypairs = gen_keypairs();
+        keypairs.insert(0, Keypair::new_ed25519(&mut rand::thread_rng()));
+        let data: &[u8] = b"hello";
+        let mut items: Vec<_> = keypairs
+            .iter()
+            .map(|keypair| (keypair.public_key(), keypair.sign(data), data))
+            .collect();
+
+        PublicKey::verify_batch(&[])?;
+        PublicKey::verify_batch(&items)?;
+
+        // an invalid ed25519 signature fails the batch
+        let mut invalid_ed25519 = items.clone();
+        invalid_ed25519[1].2 = b"goodbye";
+        assert_eq!(
+            PublicKey::verify_batch(&invalid_ed25519),
+            Err(Error::InvalidSignature)
+        );
+
+        // as does a signature of the wrong type
+        let mut mismatched = items.clone();
+        mismatched[2].1 = items[0].1.clone();
+        assert_eq!(
+            PublicKey::verify_batch(&mismatched),
+            Err(Error::InvalidSignature)
+        );
+
+        // and an invalid BLS one
+        items[2].2 = b"goodbye";
+        assert_eq!(
+            PublicKey::verify_batch(&items),
+            Err(Error::InvalidSignature)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn group_key_interpolation() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let threshold = 2;
+        let public_key_set =
+            threshold_crypto::SecretKeySet::random(threshold, &mut rng).public_keys();
+        let (group_key, share_keys) = from_public_key_set(&public_key_set, &[4, 1, 7, 3]);
+        let shares: Vec<_> = [4, 1, 7, 3].iter().copied().zip(share_keys).collect();
+
+        assert_eq!(interpolate_group_key(&shares, threshold)?, group_key);
+        assert_eq!(interpolate_group_key(&shares[1..], threshold)?, group_key);
+
+        // duplicated indices don't count twice
+        let duplicated = vec![shares[0], shares[0], shares[1]];
+        assert_eq!(
+            interpolate_group_key(&duplicated, threshold),
+            Err(Error::NotEnoughKeyShares)
+        );
+
+        let mut not_a_share = shares.clone();
+        not_a_share[0].1 = group_key;
+        assert_eq!(
+            interpolate_group_key(&not_a_share, threshold),
+            Err(Error::InvalidOperation)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn public_key_from_bytes() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let bls_key = PublicKey::Bls(threshold_crypto::SecretKey::random().public_key());
+        let secp256k1_key = PublicKey::from(gen_secp256k1_key().verifying_key());
+        for key in &[
+            Keypair::new_ed25519(&mut rng).public_key(),
+            bls_key,
+            secp256k1_key,
+        ] {
+            assert_eq!(PublicKey::from_bytes(&key.to_bytes())?, *key);
+        }
+
+        // a BLS key share is parsed as a BLS key
+        let share_key = gen_keys()[1];
+        assert_eq!(
+            PublicKey::from_bytes(&share_key.to_bytes())?.to_bytes(),
+            share_key.to_bytes()
+        );
+
+        assert!(matches!(
+            PublicKey::from_bytes(&[0; 40]),
+            Err(Error::FailedToParse(_))
+        ));
+        assert!(matches!(
+            PublicKey::from_bytes(&[]),
+            Err(Error::FailedToParse(_))
+        ));
+
+        Ok(())
+    }
 }
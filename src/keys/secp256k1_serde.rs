@@ -0,0 +1,50 @@
+// Copyright 2021 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// https://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Serde implementations for the secp256k1 types, which are serialised as their raw bytes:
+//! the compressed SEC1 encoding for public keys, and the fixed-size `r || s` for signatures.
+
+pub(super) mod public_key {
+    use k256::{ecdsa::VerifyingKey, elliptic_curve::sec1::ToEncodedPoint};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(
+        key: &VerifyingKey,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(key.to_encoded_point(true).as_bytes())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<VerifyingKey, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        VerifyingKey::from_sec1_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
+
+pub(super) mod signature {
+    use k256::ecdsa::Signature;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use signature::Signature as _;
+
+    pub(crate) fn serialize<S: Serializer>(
+        sig: &Signature,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(sig.as_bytes())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Signature, D::Error> {
+        let bytes = Vec::<u8>::deserialize(deserializer)?;
+        Signature::from_bytes(&bytes).map_err(D::Error::custom)
+    }
+}
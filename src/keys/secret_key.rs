@@ -16,7 +16,14 @@
 use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Display, Formatter};
+use subtle::{Choice, ConstantTimeEq};
 use threshold_crypto::{self, serde_impl::SerdeSecret};
+use zeroize::Zeroize;
+
+// Room for a serialised BLS secret key share: a 32-byte field element, plus any encoding
+// overhead. Serialising into fixed-size buffers avoids leaving copies of the secrets in
+// reallocated heap memory.
+const BLS_SECRET_SHARE_BUF_LEN: usize = 64;
 // TODO: remove clones. We need to restructure to hold keypair ones and only require references for this.
 /// Wrapper for different secret key types.
 #[derive(Debug, Serialize, Deserialize)]
@@ -49,6 +56,36 @@ impl SecretKey {
         })?;
         Ok(Self::Ed25519(ed25519_sk))
     }
+
+    /// Compares two secret keys in constant time, so that the comparison doesn't leak
+    /// how many bytes of the secrets match.
+    ///
+    /// Keys of different types are never equal.
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        match (self, other) {
+            (Self::Ed25519(sk), Self::Ed25519(other_sk)) => {
+                sk.as_bytes().ct_eq(other_sk.as_bytes())
+            }
+            (Self::BlsShare(sk), Self::BlsShare(other_sk)) => bls_secret_share_ct_eq(sk, other_sk),
+            _ => Choice::from(0),
+        }
+    }
+}
+
+/// Compares two BLS secret key shares in constant time. The serialised secrets are zeroed
+/// once compared.
+pub(crate) fn bls_secret_share_ct_eq(
+    sk: &SerdeSecret<threshold_crypto::SecretKeyShare>,
+    other_sk: &SerdeSecret<threshold_crypto::SecretKeyShare>,
+) -> Choice {
+    let mut bytes = [0; BLS_SECRET_SHARE_BUF_LEN];
+    let mut other_bytes = [0; BLS_SECRET_SHARE_BUF_LEN];
+    let serialised = bincode::serialize_into(&mut bytes[..], sk).is_ok()
+        & bincode::serialize_into(&mut other_bytes[..], other_sk).is_ok();
+    let is_equal = bytes.ct_eq(&other_bytes) & Choice::from(serialised as u8);
+    bytes.zeroize();
+    other_bytes.zeroize();
+    is_equal
 }
 
 impl Display for SecretKey {
@@ -56,3 +93,39 @@ impl Display for SecretKey {
         Debug::fmt(self, formatter)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    #[test]
+    fn constant_time_eq() {
+        let mut rng = rand::thread_rng();
+        let ed_sk = ed25519_dalek::SecretKey::generate(&mut rng);
+        let ed_bytes = ed_sk.to_bytes();
+        let ed25519 = SecretKey::Ed25519(ed_sk);
+        let ed25519_copy =
+            SecretKey::Ed25519(ed25519_dalek::SecretKey::from_bytes(&ed_bytes).unwrap());
+        let other_ed25519 = SecretKey::Ed25519(ed25519_dalek::SecretKey::generate(&mut rng));
+
+        let sk_set = threshold_crypto::SecretKeySet::random(1, &mut rng);
+        let bls_share = SecretKey::BlsShare(SerdeSecret(sk_set.secret_key_share(0)));
+        let bls_share_copy = SecretKey::BlsShare(SerdeSecret(sk_set.secret_key_share(0)));
+        let other_bls_share = SecretKey::BlsShare(SerdeSecret(sk_set.secret_key_share(1)));
+
+        assert!(bool::from(ed25519.ct_eq(&ed25519_copy)));
+        assert!(!bool::from(ed25519.ct_eq(&other_ed25519)));
+        assert!(bool::from(bls_share.ct_eq(&bls_share_copy)));
+        assert!(!bool::from(bls_share.ct_eq(&other_bls_share)));
+        assert!(!bool::from(ed25519.ct_eq(&bls_share)));
+    }
+
+    #[test]
+    fn bls_secret_share_fits_buffer() -> Result<()> {
+        let sk_set = threshold_crypto::SecretKeySet::random(1, &mut rand::thread_rng());
+        let sk = SerdeSecret(sk_set.secret_key_share(0));
+        assert!(utils::serialise(&sk)?.len() <= BLS_SECRET_SHARE_BUF_LEN);
+        Ok(())
+    }
+}
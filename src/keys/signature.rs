@@ -13,13 +13,16 @@
 //! `new` functions. A `PublicKey` can't be generated by itself; it must always be derived from a
 //! secret key.
 
-use crate::utils;
+use crate::{utils, Error, Result};
 
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeSet,
     fmt::{self, Debug, Formatter},
     hash::{Hash, Hasher},
+    mem,
 };
+use subtle::ConstantTimeEq;
 
 /// A signature share, with its index in the combined collection.
 #[derive(Clone, Hash, Eq, PartialEq, PartialOrd, Serialize, Deserialize, Debug)]
@@ -40,6 +43,8 @@ pub enum Signature {
     Bls(threshold_crypto::Signature),
     /// BLS signature share.
     BlsShare(SignatureShare),
+    /// Secp256k1 (ECDSA) signature.
+    Secp256k1(#[serde(with = "super::secp256k1_serde::signature")] k256::ecdsa::Signature),
 }
 
 impl Signature {
@@ -58,6 +63,59 @@ impl Signature {
             _ => None,
         }
     }
+
+    /// Returns `true` if the scheme of this signature is deterministic, i.e. signing the same
+    /// data with the same key always yields the same signature bytes.
+    ///
+    /// This tells whether signatures can safely be compared to deduplicate signed data.
+    /// - Ed25519 derives its nonce from the secret key and the message (RFC 8032).
+    /// - BLS signatures (and shares) are the secret key applied to the hash of the message, so
+    ///   there is no nonce involved at all.
+    /// - Secp256k1 ECDSA signatures are produced with deterministic nonces (RFC 6979).
+    pub fn is_deterministic_scheme(&self) -> bool {
+        match self {
+            Self::Ed25519(_) | Self::Bls(_) | Self::BlsShare(_) | Self::Secp256k1(_) => true,
+        }
+    }
+
+    /// Compares the serialised signatures in constant time, so the time taken doesn't reveal
+    /// how many bytes match, e.g. when checking a received signature against an expected one.
+    ///
+    /// Signatures of different types are never equal.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        if mem::discriminant(self) != mem::discriminant(other) {
+            return false;
+        }
+        match (utils::serialise(self), utils::serialise(other)) {
+            (Ok(bytes), Ok(other_bytes)) => bytes.ct_eq(&other_bytes).into(),
+            _ => false,
+        }
+    }
+
+    /// Combines the BLS signature `shares` of the members of `public_key_set` into a
+    /// `Signature::Bls` of the group.
+    ///
+    /// Returns `Err(Error::NotEnoughSignatureShares)` if fewer than `threshold + 1` shares are
+    /// provided, and `Err(Error::DuplicateShareIndex)` if two shares have the same index.
+    pub fn combine_shares(
+        shares: &[SignatureShare],
+        public_key_set: &threshold_crypto::PublicKeySet,
+    ) -> Result<Self> {
+        let mut indices = BTreeSet::new();
+        for share in shares {
+            if !indices.insert(share.index) {
+                return Err(Error::DuplicateShareIndex(share.index));
+            }
+        }
+        if shares.len() <= public_key_set.threshold() {
+            return Err(Error::NotEnoughSignatureShares);
+        }
+
+        public_key_set
+            .combine_signatures(shares.iter().map(|share| (share.index, &share.share)))
+            .map(Self::Bls)
+            .map_err(|_| Error::NotEnoughSignatureShares)
+    }
 }
 
 impl From<threshold_crypto::Signature> for Signature {
@@ -72,6 +130,12 @@ impl From<ed25519_dalek::Signature> for Signature {
     }
 }
 
+impl From<k256::ecdsa::Signature> for Signature {
+    fn from(sig: k256::ecdsa::Signature) -> Self {
+        Self::Secp256k1(sig)
+    }
+}
+
 impl From<SignatureShare> for Signature {
     fn from(sig: SignatureShare) -> Self {
         Self::BlsShare(sig)
@@ -99,6 +163,80 @@ impl Debug for Signature {
             Self::Ed25519(_) => write!(formatter, "Ed25519(..)"),
             Self::Bls(_) => write!(formatter, "Bls(..)"),
             Self::BlsShare(_) => write!(formatter, "BlsShare(..)"),
+            Self::Secp256k1(_) => write!(formatter, "Secp256k1(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::public_key::tests::gen_keypairs;
+    use crate::PublicKey;
+
+    #[test]
+    fn combine_shares() -> Result<()> {
+        let mut rng = rand::thread_rng();
+        let secret_key_set = threshold_crypto::SecretKeySet::random(2, &mut rng);
+        let public_key_set = secret_key_set.public_keys();
+        let data = b"hello";
+        let shares: Vec<_> = [0, 3, 5, 6]
+            .iter()
+            .map(|index| SignatureShare {
+                index: *index,
+                share: secret_key_set.secret_key_share(*index).sign(data),
+            })
+            .collect();
+
+        let signature = Signature::combine_shares(&shares, &public_key_set)?;
+        PublicKey::Bls(public_key_set.public_key()).verify(&signature, data)?;
+        assert_eq!(
+            signature,
+            Signature::combine_shares(&shares[1..], &public_key_set)?
+        );
+
+        assert_eq!(
+            Signature::combine_shares(&shares[..2], &public_key_set),
+            Err(Error::NotEnoughSignatureShares)
+        );
+        let duplicated = vec![shares[0].clone(), shares[1].clone(), shares[1].clone()];
+        assert_eq!(
+            Signature::combine_shares(&duplicated, &public_key_set),
+            Err(Error::DuplicateShareIndex(3))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn constant_time_eq() {
+        let keypairs = gen_keypairs();
+
+        for keypair in keypairs.iter() {
+            let signature = keypair.sign(b"hello");
+            let same = keypair.sign(b"hello");
+            let other = keypair.sign(b"world");
+            assert_eq!(signature.ct_eq(&same), signature == same);
+            assert!(signature.ct_eq(&same));
+            assert_eq!(signature.ct_eq(&other), signature == other);
+            assert!(!signature.ct_eq(&other));
+        }
+
+        let ed25519 = keypairs[0].sign(b"hello");
+        let bls_share = keypairs[1].sign(b"hello");
+        assert!(!ed25519.ct_eq(&bls_share));
+    }
+
+    #[test]
+    fn deterministic_signatures() {
+        let keypairs = gen_keypairs();
+
+        let data = b"hello";
+        for keypair in keypairs {
+            let sig1 = keypair.sign(data);
+            let sig2 = keypair.sign(data);
+            assert!(sig1.is_deterministic_scheme());
+            assert_eq!(sig1, sig2);
         }
     }
 }
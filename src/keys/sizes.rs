@@ -0,0 +1,77 @@
+// Copyright 2021 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// https://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Byte lengths of the keys and signatures, as returned by their `to_bytes` functions.
+
+/// Length of an Ed25519 public key.
+pub const ED25519_PUBLIC_LEN: usize = ed25519_dalek::PUBLIC_KEY_LENGTH;
+/// Length of an Ed25519 secret key.
+pub const ED25519_SECRET_LEN: usize = ed25519_dalek::SECRET_KEY_LENGTH;
+/// Length of an Ed25519 signature.
+pub const ED25519_SIG_LEN: usize = ed25519_dalek::SIGNATURE_LENGTH;
+/// Length of a BLS public key, or public key share.
+pub const BLS_PUBLIC_LEN: usize = threshold_crypto::PK_SIZE;
+/// Length of a BLS signature, or signature share.
+pub const BLS_SIG_LEN: usize = threshold_crypto::SIG_SIZE;
+/// Length of a secp256k1 public key, SEC1-encoded and compressed.
+pub const SECP256K1_PUBLIC_LEN: usize = 33;
+/// Length of a secp256k1 public key, SEC1-encoded and uncompressed.
+pub const SECP256K1_PUBLIC_UNCOMPRESSED_LEN: usize = 65;
+/// Length of a secp256k1 (ECDSA) signature, `r || s`.
+pub const SECP256K1_SIG_LEN: usize = 64;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::public_key::tests::gen_secp256k1_key;
+    use crate::{Keypair, PublicKey, Signature};
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+    use signature::Signer;
+
+    #[test]
+    fn sizes_match_generated_keys() {
+        let mut rng = rand::thread_rng();
+        let sk_set = threshold_crypto::SecretKeySet::random(1, &mut rng);
+        let data = b"hello";
+
+        let ed25519 = Keypair::new_ed25519(&mut rng);
+        assert_eq!(ed25519.public_key().to_bytes().len(), ED25519_PUBLIC_LEN);
+        match ed25519.sign(data) {
+            Signature::Ed25519(sig) => assert_eq!(sig.to_bytes().len(), ED25519_SIG_LEN),
+            _ => panic!("expected an Ed25519 signature"),
+        }
+        if let Ok(crate::SecretKey::Ed25519(sk)) = ed25519.secret_key() {
+            assert_eq!(sk.to_bytes().len(), ED25519_SECRET_LEN);
+        } else {
+            panic!("expected an Ed25519 secret key");
+        }
+
+        let bls = PublicKey::Bls(sk_set.public_keys().public_key());
+        assert_eq!(bls.to_bytes().len(), BLS_PUBLIC_LEN);
+        let bls_share = Keypair::new_bls_share(0, sk_set.secret_key_share(0), sk_set.public_keys());
+        assert_eq!(bls_share.public_key().to_bytes().len(), BLS_PUBLIC_LEN);
+        match bls_share.sign(data) {
+            Signature::BlsShare(sig) => assert_eq!(sig.share.to_bytes().len(), BLS_SIG_LEN),
+            _ => panic!("expected a BLS signature share"),
+        }
+
+        let secp256k1_signing_key = gen_secp256k1_key();
+        let secp256k1 = secp256k1_signing_key.verifying_key();
+        assert_eq!(
+            PublicKey::from(secp256k1).to_bytes().len(),
+            SECP256K1_PUBLIC_LEN
+        );
+        assert_eq!(
+            secp256k1.to_encoded_point(false).as_bytes().len(),
+            SECP256K1_PUBLIC_UNCOMPRESSED_LEN
+        );
+        let sig: k256::ecdsa::Signature = secp256k1_signing_key.sign(data);
+        assert_eq!(sig.as_ref().len(), SECP256K1_SIG_LEN);
+    }
+}
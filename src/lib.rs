@@ -46,9 +46,12 @@ pub use chunk::{
     MAX_CHUNK_SIZE_IN_BYTES,
 };
 pub use errors::{Error, Result};
+/// Byte lengths of the keys and signatures.
+pub use keys::sizes as key_sizes;
 pub use keys::{
-    BlsKeypairShare, Keypair, NodeKeypairs, OwnerType, PublicKey, SecretKey, Signature,
-    SignatureShare, Signing,
+    from_public_key_set, interpolate_group_key, BlsKeySet, BlsKeypairShare, KeyScheme, Keypair,
+    NodeIdentity, NodeKeypairs, OwnerType, PublicKey, SecretKey, Signature, SignatureShare,
+    Signing,
 };
 pub use map::{
     Action as MapAction, Address as MapAddress, Data as Map, Entries as MapEntries,
@@ -62,7 +65,8 @@ pub use register::Address as RegisterAddress;
 pub use rewards::{AccumulatingReward, NodeAge, RewardAccumulation, RewardProposal};
 pub use section::SectionElders;
 pub use sequence::{
-    Action as SequenceAction, Address as SequenceAddress, Data as Sequence, DataOp as SequenceOp,
+    Action as SequenceAction, Address as SequenceAddress, Cursor as SequenceCursor,
+    Data as Sequence, DataOp as SequenceOp, EffectivePermissions as SequenceEffectivePermissions,
     Entries as SequenceEntries, Entry as SequenceEntry, Index as SequenceIndex,
     Kind as SequenceKind, Permissions as SequencePermissions, Policy as SequencePolicy,
     PrivatePermissions as SequencePrivatePermissions, PrivatePolicy as SequencePrivatePolicy,
@@ -71,6 +75,7 @@ pub use sequence::{
 };
 pub use token::Token;
 pub use transfer::*;
+pub use utils::{deserialise_compressed, from_json, serialise_compressed, to_json};
 
 use register::Register;
 use serde::{Deserialize, Serialize};
@@ -104,6 +109,99 @@ pub enum DataAddress {
     Register(RegisterAddress),
 }
 
+/// Address of any data on the network, for code handling addresses of all data types alike.
+pub trait NetworkAddress {
+    /// Returns the name.
+    fn name(&self) -> &XorName;
+    /// Returns true if the data is public.
+    fn is_public(&self) -> bool;
+}
+
+impl NetworkAddress for ChunkAddress {
+    fn name(&self) -> &XorName {
+        ChunkAddress::name(self)
+    }
+
+    fn is_public(&self) -> bool {
+        ChunkAddress::is_public(self)
+    }
+}
+
+impl NetworkAddress for MapAddress {
+    fn name(&self) -> &XorName {
+        MapAddress::name(self)
+    }
+
+    // Maps are always private.
+    fn is_public(&self) -> bool {
+        false
+    }
+}
+
+impl NetworkAddress for SequenceAddress {
+    fn name(&self) -> &XorName {
+        SequenceAddress::name(self)
+    }
+
+    fn is_public(&self) -> bool {
+        SequenceAddress::is_public(self)
+    }
+}
+
+impl NetworkAddress for RegisterAddress {
+    fn name(&self) -> &XorName {
+        RegisterAddress::name(self)
+    }
+
+    fn is_public(&self) -> bool {
+        RegisterAddress::is_public(self)
+    }
+}
+
+impl NetworkAddress for DataAddress {
+    fn name(&self) -> &XorName {
+        match self {
+            Self::Chunk(address) => NetworkAddress::name(address),
+            Self::Map(address) => NetworkAddress::name(address),
+            Self::Sequence(address) => NetworkAddress::name(address),
+            Self::Register(address) => NetworkAddress::name(address),
+        }
+    }
+
+    fn is_public(&self) -> bool {
+        match self {
+            Self::Chunk(address) => NetworkAddress::is_public(address),
+            Self::Map(address) => NetworkAddress::is_public(address),
+            Self::Sequence(address) => NetworkAddress::is_public(address),
+            Self::Register(address) => NetworkAddress::is_public(address),
+        }
+    }
+}
+
+impl From<ChunkAddress> for DataAddress {
+    fn from(address: ChunkAddress) -> Self {
+        Self::Chunk(address)
+    }
+}
+
+impl From<MapAddress> for DataAddress {
+    fn from(address: MapAddress) -> Self {
+        Self::Map(address)
+    }
+}
+
+impl From<SequenceAddress> for DataAddress {
+    fn from(address: SequenceAddress) -> Self {
+        Self::Sequence(address)
+    }
+}
+
+impl From<RegisterAddress> for DataAddress {
+    fn from(address: RegisterAddress) -> Self {
+        Self::Register(address)
+    }
+}
+
 impl Data {
     /// Returns true if public.
     pub fn is_public(&self) -> bool {
@@ -144,3 +242,47 @@ impl From<Register> for Data {
         Self::Register(data)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn network_address() -> Result<()> {
+        let name = XorName::random();
+        let addresses: [(Box<dyn NetworkAddress>, DataAddress, bool); 4] = [
+            (
+                Box::new(ChunkAddress::Public(name)),
+                ChunkAddress::Public(name).into(),
+                true,
+            ),
+            (
+                Box::new(MapAddress::Seq { name, tag: 1 }),
+                MapAddress::Seq { name, tag: 1 }.into(),
+                false,
+            ),
+            (
+                Box::new(SequenceAddress::Private { name, tag: 1 }),
+                SequenceAddress::Private { name, tag: 1 }.into(),
+                false,
+            ),
+            (
+                Box::new(RegisterAddress::Public { name, tag: 1 }),
+                RegisterAddress::Public { name, tag: 1 }.into(),
+                true,
+            ),
+        ];
+
+        for (address, data_address, is_public) in addresses.iter() {
+            let dispatched: &dyn NetworkAddress = data_address;
+            for address in [address.as_ref(), dispatched] {
+                assert_eq!(address.name(), &name);
+                assert_eq!(address.is_public(), *is_public);
+            }
+            let parsed: DataAddress = utils::deserialise(&utils::serialise(data_address)?)?;
+            assert_eq!(&parsed, data_address);
+        }
+
+        Ok(())
+    }
+}
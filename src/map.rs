@@ -1033,6 +1033,16 @@ impl SeqEntryActions {
     pub fn add_action(&mut self, key: Vec<u8>, action: SeqEntryAction) {
         let _ = self.actions.insert(key, action);
     }
+
+    /// Returns the number of actions.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if there are no actions.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
 }
 
 impl From<SeqEntryActions> for BTreeMap<Vec<u8>, SeqEntryAction> {
@@ -1093,6 +1103,16 @@ impl UnseqEntryActions {
     pub fn add_action(&mut self, key: Vec<u8>, action: UnseqEntryAction) {
         let _ = self.actions.insert(key, action);
     }
+
+    /// Returns the number of actions.
+    pub fn len(&self) -> usize {
+        self.actions.len()
+    }
+
+    /// Returns true if there are no actions.
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
 }
 
 impl From<UnseqEntryActions> for BTreeMap<Vec<u8>, UnseqEntryAction> {
@@ -1124,6 +1144,19 @@ impl EntryActions {
             EntryActions::Unseq(_) => Kind::Unseq,
         }
     }
+
+    /// Returns the number of actions.
+    pub fn len(&self) -> usize {
+        match self {
+            EntryActions::Seq(actions) => actions.len(),
+            EntryActions::Unseq(actions) => actions.len(),
+        }
+    }
+
+    /// Returns true if there are no actions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 impl From<SeqEntryActions> for EntryActions {
@@ -1166,8 +1199,8 @@ impl From<UnseqEntries> for Entries {
 
 #[cfg(test)]
 mod tests {
-    use super::{Address, XorName};
-    use crate::Result;
+    use super::{Address, EntryActions, SeqEntryActions, UnseqEntryActions, XorName};
+    use crate::{utils, Result};
 
     #[test]
     fn zbase32_encode_decode_map_address() -> Result<()> {
@@ -1178,4 +1211,31 @@ mod tests {
         assert_eq!(address, decoded);
         Ok(())
     }
+
+    #[test]
+    fn entry_actions_serialisation() -> Result<()> {
+        let seq_actions = SeqEntryActions::new()
+            .ins(b"key1".to_vec(), b"value1".to_vec(), 0)
+            .update(b"key2".to_vec(), b"value2".to_vec(), 3)
+            .del(b"key3".to_vec(), 1);
+        assert_eq!(seq_actions.len(), 3);
+        assert!(SeqEntryActions::new().is_empty());
+
+        let unseq_actions = UnseqEntryActions::new()
+            .ins(b"key1".to_vec(), b"value1".to_vec())
+            .del(b"key2".to_vec());
+        assert_eq!(unseq_actions.len(), 2);
+        assert!(UnseqEntryActions::new().is_empty());
+
+        for actions in &[
+            EntryActions::from(seq_actions),
+            EntryActions::from(unseq_actions),
+        ] {
+            let decoded: EntryActions = utils::deserialise(&utils::serialise(actions)?)?;
+            assert_eq!(decoded.len(), actions.len());
+            assert_eq!(&decoded, actions);
+        }
+
+        Ok(())
+    }
 }
@@ -127,12 +127,90 @@ pub enum Index {
     FromEnd(u64),
 }
 
+impl Index {
+    /// Resolves the index into an absolute one, against a Sequence of `len` entries.
+    /// Returns `None` if it is out of bounds, i.e. `FromStart(n)` with `n > len`, or
+    /// `FromEnd(n)` with `n > len`. Note `len` itself is in bounds, as the end of a range.
+    pub fn to_absolute(self, len: u64) -> Option<u64> {
+        match self {
+            Index::FromStart(index) if index <= len => Some(index),
+            Index::FromStart(_) => None,
+            Index::FromEnd(index) => len.checked_sub(index),
+        }
+    }
+
+    /// Returns true if the index is relative to the end.
+    pub fn is_from_end(self) -> bool {
+        matches!(self, Index::FromEnd(_))
+    }
+}
+
 impl From<u64> for Index {
     fn from(index: u64) -> Self {
         Index::FromStart(index)
     }
 }
 
+/// Cursor for fetching the entries of a Sequence page by page, from the start.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cursor {
+    address: Address,
+    next: u64,
+    page_size: u64,
+    exhausted: bool,
+}
+
+impl Cursor {
+    /// Constructs a cursor at the start of the Sequence at `address`.
+    /// A `page_size` of 0 is treated as 1.
+    pub fn new(address: Address, page_size: u64) -> Self {
+        Self {
+            address,
+            next: 0,
+            page_size: page_size.max(1),
+            exhausted: false,
+        }
+    }
+
+    /// Returns the address of the Sequence.
+    pub fn address(&self) -> &Address {
+        &self.address
+    }
+
+    /// Returns the index of the next entry to fetch.
+    pub fn next(&self) -> Index {
+        Index::FromStart(self.next)
+    }
+
+    /// Returns the page size.
+    pub fn page_size(&self) -> u64 {
+        self.page_size
+    }
+
+    /// Returns the `[start, end)` range of the next page.
+    pub fn range(&self) -> (Index, Index) {
+        (
+            Index::FromStart(self.next),
+            Index::FromStart(self.next.saturating_add(self.page_size)),
+        )
+    }
+
+    /// Moves the cursor past the `fetched` entries of the last page.
+    /// The cursor is exhausted once a page holds fewer than `page_size` entries.
+    pub fn advance(&mut self, fetched: usize) {
+        let fetched = fetched as u64;
+        self.next = self.next.saturating_add(fetched);
+        if fetched < self.page_size {
+            self.exhausted = true;
+        }
+    }
+
+    /// Returns true if there are no more entries to fetch.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
 /// Set of public permissions for a user.
 #[derive(Copy, Clone, Serialize, Deserialize, PartialEq, PartialOrd, Ord, Eq, Hash, Debug)]
 pub struct PublicPermissions {
@@ -215,6 +293,20 @@ pub struct PublicPolicy {
 }
 
 impl PublicPolicy {
+    /// Returns true if `action` is allowed for `user`.
+    ///
+    /// A user without permissions of their own falls back to those of `User::Anyone`, while
+    /// permissions explicitly denied to a user override those granted to `User::Anyone`.
+    pub fn is_allowed(&self, user: &User, action: Action) -> bool {
+        match user {
+            User::Key(key) => self.is_action_allowed(*key, action).is_ok(),
+            User::Anyone => {
+                action == Action::Read
+                    || self.is_action_allowed_by_user(&User::Anyone, action) == Some(true)
+            }
+        }
+    }
+
     /// Returns `Some(true)` if `action` is allowed for the provided user and `Some(false)` if it's
     /// not permitted. `None` means that default permissions should be applied.
     fn is_action_allowed_by_user(&self, user: &User, action: Action) -> Option<bool> {
@@ -234,6 +326,13 @@ pub struct PrivatePolicy {
     pub permissions: BTreeMap<PublicKey, PrivatePermissions>,
 }
 
+impl PrivatePolicy {
+    /// Returns true if `action` is allowed for the user with the given `key`.
+    pub fn is_allowed(&self, key: &PublicKey, action: Action) -> bool {
+        self.is_action_allowed(*key, action).is_ok()
+    }
+}
+
 pub trait Perm {
     /// Returns true if `action` is allowed for the provided user.
     fn is_action_allowed(&self, requester: PublicKey, action: Action) -> Result<()>;
@@ -308,6 +407,29 @@ impl Perm for PrivatePolicy {
     }
 }
 
+/// The actions a user is currently allowed to perform on a Sequence, resolved from its
+/// policy, including the owner's implicit permissions.
+#[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Debug)]
+pub struct EffectivePermissions {
+    /// Whether the user is the owner, who is allowed all actions.
+    pub is_owner: bool,
+    /// Whether the user can read the entries.
+    pub can_read: bool,
+    /// Whether the user can append entries.
+    pub can_append: bool,
+}
+
+impl EffectivePermissions {
+    /// Resolves the permissions of `user` from `policy`.
+    pub fn resolve<P: Perm>(policy: &P, user: PublicKey) -> Self {
+        Self {
+            is_owner: *policy.owner() == user,
+            can_read: policy.is_action_allowed(user, Action::Read).is_ok(),
+            can_append: policy.is_action_allowed(user, Action::Append).is_ok(),
+        }
+    }
+}
+
 /// Wrapper type for permissions, which can be public or private.
 #[derive(Clone, Serialize, Deserialize, PartialEq, PartialOrd, Ord, Eq, Hash, Debug)]
 pub enum Policy {
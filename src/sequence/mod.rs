@@ -9,11 +9,12 @@
 
 mod metadata;
 mod seq_crdt;
+mod snapshot;
 
 use crate::{Error, PublicKey, Result};
 pub use metadata::{
-    Action, Address, Entries, Entry, Index, Kind, Perm, Permissions, Policy, PrivatePermissions,
-    PrivatePolicy, PublicPermissions, PublicPolicy, User,
+    Action, Address, Cursor, EffectivePermissions, Entries, Entry, Index, Kind, Perm, Permissions,
+    Policy, PrivatePermissions, PrivatePolicy, PublicPermissions, PublicPolicy, User,
 };
 use seq_crdt::{CrdtOperation, SequenceCrdt};
 use serde::{Deserialize, Serialize};
@@ -106,6 +107,32 @@ impl Data {
         }
     }
 
+    /// Constructs a new Sequence Data at `address` which inherits the owner and
+    /// users permissions currently set on the `parent` Sequence.
+    /// The 'authority' and 'actor' are used the same way as in `new_public`/`new_private`.
+    ///
+    /// The kind of `address` must match the kind of the parent, so the permissions of
+    /// a private parent never end up on a public child. Returns `Err(Error::InvalidOperation)`
+    /// otherwise.
+    pub fn new_inheriting(
+        authority: PublicKey,
+        actor: ActorType,
+        address: Address,
+        parent: &Data,
+    ) -> Result<Self> {
+        let data = match (address, &parent.data) {
+            (Address::Public { .. }, SeqData::Public(parent)) => {
+                SeqData::Public(PublicSeqData::new(actor, address, parent.policy().clone()))
+            }
+            (Address::Private { .. }, SeqData::Private(parent)) => {
+                SeqData::Private(PrivateSeqData::new(actor, address, parent.policy().clone()))
+            }
+            _ => return Err(Error::InvalidOperation),
+        };
+
+        Ok(Self { authority, data })
+    }
+
     /// Returns the address.
     pub fn address(&self) -> &Address {
         match &self.data {
@@ -159,6 +186,8 @@ impl Data {
 
     /// Gets a list of items which are within the given indices.
     /// Note the range of items is [start, end), i.e. the end index is not inclusive.
+    /// A `FromStart` end past the last item is clamped to it, while an end before the start
+    /// (including a `FromEnd` end before the first item) yields `None`.
     pub fn in_range(
         &self,
         start: Index,
@@ -195,6 +224,23 @@ impl Data {
         })
     }
 
+    /// Returns a checksum over all the entries in index order, so two replicas can detect
+    /// divergence with a single comparison before diffing their entries.
+    pub fn entries_checksum(&self, requester: Option<PublicKey>) -> Result<u64> {
+        self.check_permission(Action::Read, requester)?;
+
+        Ok(match &self.data {
+            SeqData::Public(data) => data.entries_checksum(),
+            SeqData::Private(data) => data.entries_checksum(),
+        })
+    }
+
+    /// Returns the current (last) entry.
+    /// Returns `Err(Error::NoSuchEntry)` if the Sequence is empty.
+    pub fn current_entry(&self, requester: Option<PublicKey>) -> Result<&Entry> {
+        self.last_entry(requester)?.ok_or(Error::NoSuchEntry)
+    }
+
     /// Generate unsigned crdt op, adding the new entry.
     pub fn create_unsigned_append_op(&mut self, entry: Entry) -> Result<DataOp<Entry>> {
         self.check_permission(Action::Append, None)?;
@@ -258,6 +304,15 @@ impl Data {
         }
     }
 
+    /// Returns the actions `user` is currently allowed to perform on this Sequence.
+    /// The owner is allowed all of them.
+    pub fn my_permissions(&self, user: PublicKey) -> EffectivePermissions {
+        match &self.data {
+            SeqData::Public(data) => EffectivePermissions::resolve(data.policy(), user),
+            SeqData::Private(data) => EffectivePermissions::resolve(data.policy(), user),
+        }
+    }
+
     /// Returns the owner of the data.
     pub fn owner(&self) -> PublicKey {
         match &self.data {
@@ -270,14 +325,35 @@ impl Data {
     pub fn replica_authority(&self) -> PublicKey {
         self.authority
     }
+
+    /// Exports the state of this replica (entries, policy and authority) into a portable
+    /// snapshot, e.g. for backups or migrations.
+    ///
+    /// The snapshot is a versioned JSON document holding only that state, not the CRDT
+    /// internals, so it can still be imported after the in-memory types change. On import,
+    /// the entries are re-appended by the replica's actor: the imported replica has the same
+    /// entries, but not the CRDT history of replicas which were appended to concurrently.
+    pub fn export(&self) -> Result<Vec<u8>> {
+        snapshot::export(self)
+    }
+
+    /// Imports a Sequence from a snapshot created with `export`, by any supported version
+    /// of the snapshot format.
+    ///
+    /// Returns `Err(Error::FailedToParse)` if the snapshot is corrupted or was
+    /// produced by an unsupported version of the format.
+    pub fn import(snapshot: &[u8]) -> Result<Self> {
+        snapshot::import(snapshot)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        utils, Error, Keypair, Result, Sequence, SequenceAddress, SequenceEntry, SequenceIndex,
-        SequenceKind, SequenceOp, SequencePermissions, SequencePrivatePermissions,
-        SequencePrivatePolicy, SequencePublicPermissions, SequencePublicPolicy, SequenceUser,
+        utils, Error, Keypair, Result, Sequence, SequenceAction, SequenceAddress, SequenceCursor,
+        SequenceEntry, SequenceIndex, SequenceKind, SequenceOp, SequencePermissions,
+        SequencePrivatePermissions, SequencePrivatePolicy, SequencePublicPermissions,
+        SequencePublicPolicy, SequenceUser,
     };
     use anyhow::anyhow;
     use proptest::prelude::*;
@@ -285,6 +361,140 @@ mod tests {
     use std::{collections::BTreeMap, sync::Arc};
     use xor_name::XorName;
 
+    #[test]
+    fn zbase32_encode_decode_sequence_address() -> Result<()> {
+        let name = XorName::random();
+        for address in [
+            SequenceAddress::Public { name, tag: 15000 },
+            SequenceAddress::Private { name, tag: 15000 },
+        ] {
+            let encoded = address.encode_to_zbase32()?;
+            let decoded = SequenceAddress::decode_from_zbase32(&encoded)?;
+            assert_eq!(decoded, address);
+            assert_eq!(decoded.kind(), address.kind());
+        }
+
+        // trailing bytes don't round-trip
+        let mut bytes = utils::serialise(&SequenceAddress::Public { name, tag: 15000 })?;
+        bytes.push(0);
+        let encoded = multibase::encode(multibase::Base::Base32Z, &bytes);
+        assert!(matches!(
+            SequenceAddress::decode_from_zbase32(&encoded),
+            Err(Error::FailedToParse(_))
+        ));
+        assert!(matches!(
+            SequenceAddress::decode_from_zbase32("not zbase32"),
+            Err(Error::FailedToParse(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_cursor_pages() -> Result<()> {
+        let mut replicas = create_public_seq_replicas(1);
+        let (authority_keypair, sequence) = &mut replicas[0];
+        for i in 0..25u8 {
+            let op = sign_sequence_op(
+                sequence.create_unsigned_append_op(vec![i])?,
+                authority_keypair,
+            )?;
+            sequence.apply_op(op)?;
+        }
+
+        let mut cursor = SequenceCursor::new(*sequence.address(), 10);
+        let mut pages = Vec::new();
+        while !cursor.is_exhausted() {
+            let (start, end) = cursor.range();
+            let page = sequence.in_range(start, end, None)?.unwrap_or_default();
+            cursor.advance(page.len());
+            pages.push(page);
+        }
+
+        assert_eq!(pages.iter().map(Vec::len).collect::<Vec<_>>(), [10, 10, 5]);
+        let entries: Vec<_> = pages.into_iter().flatten().collect();
+        assert_eq!(entries, (0..25u8).map(|i| vec![i]).collect::<Vec<_>>());
+        assert_eq!(cursor.next(), SequenceIndex::FromStart(25));
+        assert_eq!(cursor.address(), sequence.address());
+
+        // an exact multiple of the page size needs a final empty page
+        let mut cursor = SequenceCursor::new(*sequence.address(), 5);
+        for _ in 0..5 {
+            cursor.advance(5);
+            assert!(!cursor.is_exhausted());
+        }
+        let (start, end) = cursor.range();
+        assert_eq!(sequence.in_range(start, end, None)?, None);
+        cursor.advance(0);
+        assert!(cursor.is_exhausted());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_policy_is_allowed() {
+        let owner = Keypair::new_ed25519(&mut OsRng).public_key();
+        let denied = Keypair::new_ed25519(&mut OsRng).public_key();
+        let other = Keypair::new_ed25519(&mut OsRng).public_key();
+
+        let mut permissions = BTreeMap::new();
+        let _ = permissions.insert(SequenceUser::Anyone, SequencePublicPermissions::new(true));
+        let _ = permissions.insert(
+            SequenceUser::Key(denied),
+            SequencePublicPermissions::new(false),
+        );
+        let policy = SequencePublicPolicy { owner, permissions };
+
+        // an explicit denial overrides `Anyone`
+        assert!(!policy.is_allowed(&SequenceUser::Key(denied), SequenceAction::Append));
+        assert!(policy.is_allowed(&SequenceUser::Key(denied), SequenceAction::Read));
+        // no entry of their own falls back to `Anyone`
+        assert!(policy.is_allowed(&SequenceUser::Key(other), SequenceAction::Append));
+        assert!(policy.is_allowed(&SequenceUser::Anyone, SequenceAction::Append));
+        assert!(policy.is_allowed(&SequenceUser::Key(owner), SequenceAction::Append));
+
+        let mut permissions = BTreeMap::new();
+        let _ = permissions.insert(denied, SequencePrivatePermissions::new(true, false));
+        let policy = SequencePrivatePolicy { owner, permissions };
+
+        assert!(policy.is_allowed(&owner, SequenceAction::Append));
+        assert!(policy.is_allowed(&denied, SequenceAction::Read));
+        assert!(!policy.is_allowed(&denied, SequenceAction::Append));
+        assert!(!policy.is_allowed(&other, SequenceAction::Read));
+    }
+
+    #[test]
+    fn sequence_appends_at_same_index_on_two_replicas_both_merge() -> Result<()> {
+        let owner = Keypair::new_ed25519(&mut OsRng).public_key();
+        let mut permissions = BTreeMap::default();
+        let _ = permissions.insert(SequenceUser::Anyone, SequencePublicPermissions::new(true));
+        let policy = SequencePublicPolicy { owner, permissions };
+        let mut replicas = gen_pub_seq_replicas(None, XorName::random(), 43_000, Some(policy), 2);
+        let (keypair2, mut replica2) = replicas.remove(1);
+        let (keypair1, mut replica1) = replicas.remove(0);
+
+        // both writers see an empty Sequence and append to it
+        assert_eq!(replica1.len(None)?, 0);
+        assert_eq!(replica2.len(None)?, 0);
+        let op1 = sign_sequence_op(
+            replica1.create_unsigned_append_op(b"first".to_vec())?,
+            &keypair1,
+        )?;
+        let op2 = sign_sequence_op(
+            replica2.create_unsigned_append_op(b"second".to_vec())?,
+            &keypair2,
+        )?;
+        replica1.apply_op(op1.clone())?;
+        replica2.apply_op(op2.clone())?;
+
+        // ops carry no expected index, so neither append can be rejected on merge
+        replica1.apply_op(op2)?;
+        replica2.apply_op(op1)?;
+        verify_data_convergence(vec![replica1, replica2], 2)?;
+
+        Ok(())
+    }
+
     #[test]
     fn sequence_create_public() {
         let sequence_name = XorName::random();
@@ -433,10 +643,22 @@ mod tests {
         );
 
         let last_entry = sequence.in_range(index_2, end_index, None)?;
-        assert_eq!(last_entry, Some(vec![entry3]));
+        assert_eq!(last_entry, Some(vec![entry3.clone()]));
 
         let second_entry = sequence.in_range(index_1, SequenceIndex::FromEnd(1), None)?;
-        assert_eq!(second_entry, Some(vec![entry2]));
+        assert_eq!(second_entry, Some(vec![entry2.clone()]));
+
+        // a `FromStart` end past the last entry is clamped to it
+        let past_end = sequence.in_range(index_1, SequenceIndex::FromStart(10), None)?;
+        assert_eq!(past_end, Some(vec![entry2, entry3]));
+
+        // an end before the start yields nothing rather than underflowing
+        let reversed = sequence.in_range(index_2, index_1, None)?;
+        assert_eq!(reversed, None);
+
+        // a `FromEnd` end before the first entry is out of bounds
+        let before_start = sequence.in_range(index_0, SequenceIndex::FromEnd(4), None)?;
+        assert_eq!(before_start, None);
 
         let index_3 = SequenceIndex::FromStart(3);
         match sequence.in_range(index_3, index_3, None) {
@@ -730,6 +952,263 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn sequence_export_import() -> anyhow::Result<()> {
+        let authority_keypair = Keypair::new_ed25519(&mut OsRng);
+        let authority = authority_keypair.public_key();
+        let user = Keypair::new_ed25519(&mut OsRng).public_key();
+
+        let mut perms = BTreeMap::default();
+        let _ = perms.insert(user, SequencePrivatePermissions::new(true, false));
+        let mut sequence = create_private_seq_replica_with(
+            Some(authority_keypair.clone()),
+            Some(SequencePrivatePolicy {
+                owner: authority,
+                permissions: perms,
+            }),
+        );
+
+        for entry in &[b"value0", b"value1", b"value2"] {
+            let op = sign_sequence_op(
+                sequence.create_unsigned_append_op(entry.to_vec())?,
+                &authority_keypair,
+            )?;
+            sequence.apply_op(op)?;
+        }
+
+        let snapshot = sequence.export()?;
+        let imported = Sequence::import(&snapshot)?;
+        assert_eq!(imported, sequence);
+        assert_eq!(imported.len(None)?, 3);
+        assert_eq!(
+            imported.permissions(SequenceUser::Key(user), None)?,
+            SequencePermissions::Private(SequencePrivatePermissions::new(true, false))
+        );
+
+        // a truncated snapshot can't be imported
+        let corrupted = &snapshot[..snapshot.len() - 1];
+        assert!(matches!(
+            Sequence::import(corrupted),
+            Err(Error::FailedToParse(_))
+        ));
+
+        // neither can a snapshot of an unknown version
+        let mut json: serde_json::Value = serde_json::from_slice(&snapshot)?;
+        assert_eq!(json["version"], 1);
+        json["version"] = 2.into();
+        assert!(matches!(
+            Sequence::import(&serde_json::to_vec(&json)?),
+            Err(Error::FailedToParse(_))
+        ));
+        assert!(matches!(
+            Sequence::import(b"not a snapshot"),
+            Err(Error::FailedToParse(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_export_import_public() -> anyhow::Result<()> {
+        let authority_keypair = Keypair::new_ed25519(&mut OsRng);
+        let user = Keypair::new_ed25519(&mut OsRng).public_key();
+
+        let mut perms = BTreeMap::default();
+        let _ = perms.insert(SequenceUser::Anyone, SequencePublicPermissions::new(true));
+        let _ = perms.insert(
+            SequenceUser::Key(user),
+            SequencePublicPermissions::new(false),
+        );
+        let mut sequence = create_public_seq_replica_with(
+            Some(authority_keypair.clone()),
+            Some(SequencePublicPolicy {
+                owner: authority_keypair.public_key(),
+                permissions: perms,
+            }),
+        );
+        for entry in &[b"value0", b"value1"] {
+            let op = sign_sequence_op(
+                sequence.create_unsigned_append_op(entry.to_vec())?,
+                &authority_keypair,
+            )?;
+            sequence.apply_op(op)?;
+        }
+
+        let imported = Sequence::import(&sequence.export()?)?;
+        assert_eq!(imported, sequence);
+        assert_eq!(
+            imported.permissions(SequenceUser::Key(user), None)?,
+            SequencePermissions::Public(SequencePublicPermissions::new(false))
+        );
+
+        // the imported replica keeps appending after the restored entries
+        let mut imported = imported;
+        let op = sign_sequence_op(
+            imported.create_unsigned_append_op(b"value2".to_vec())?,
+            &authority_keypair,
+        )?;
+        imported.apply_op(op)?;
+        assert_eq!(
+            imported.in_range(SequenceIndex::FromStart(0), SequenceIndex::FromEnd(0), None)?,
+            Some(vec![
+                b"value0".to_vec(),
+                b"value1".to_vec(),
+                b"value2".to_vec()
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_entries_checksum() -> anyhow::Result<()> {
+        let authority_keypair = Keypair::new_ed25519(&mut OsRng);
+        let mut replicas = gen_pub_seq_replicas(
+            Some(authority_keypair.clone()),
+            XorName::random(),
+            43_000,
+            None,
+            2,
+        );
+        let (_, mut replica2) = replicas.remove(1);
+        let (_, mut replica1) = replicas.remove(0);
+        assert_eq!(
+            replica1.entries_checksum(None)?,
+            replica2.entries_checksum(None)?
+        );
+
+        for entry in [b"entry0", b"entry1"].iter() {
+            let op = sign_sequence_op(
+                replica1.create_unsigned_append_op(entry.to_vec())?,
+                &authority_keypair,
+            )?;
+            replica1.apply_op(op.clone())?;
+            replica2.apply_op(op)?;
+        }
+        assert_eq!(
+            replica1.entries_checksum(None)?,
+            replica2.entries_checksum(None)?
+        );
+
+        // one more entry on replica1 only
+        let op = sign_sequence_op(
+            replica1.create_unsigned_append_op(b"entry2".to_vec())?,
+            &authority_keypair,
+        )?;
+        replica1.apply_op(op)?;
+        assert_ne!(
+            replica1.entries_checksum(None)?,
+            replica2.entries_checksum(None)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_index_to_absolute() {
+        assert_eq!(SequenceIndex::FromStart(0).to_absolute(5), Some(0));
+        assert_eq!(SequenceIndex::FromStart(5).to_absolute(5), Some(5));
+        assert_eq!(SequenceIndex::FromStart(6).to_absolute(5), None);
+        assert_eq!(SequenceIndex::FromEnd(0).to_absolute(5), Some(5));
+        assert_eq!(SequenceIndex::FromEnd(5).to_absolute(5), Some(0));
+        assert_eq!(SequenceIndex::FromEnd(6).to_absolute(5), None);
+        assert_eq!(SequenceIndex::FromEnd(0).to_absolute(0), Some(0));
+
+        assert!(SequenceIndex::FromEnd(0).is_from_end());
+        assert!(!SequenceIndex::FromStart(0).is_from_end());
+    }
+
+    #[test]
+    fn sequence_my_permissions() {
+        let owner_keypair = Keypair::new_ed25519(&mut OsRng);
+        let owner = owner_keypair.public_key();
+        let reader = Keypair::new_ed25519(&mut OsRng).public_key();
+        let stranger = Keypair::new_ed25519(&mut OsRng).public_key();
+
+        let mut perms = BTreeMap::default();
+        let _ = perms.insert(reader, SequencePrivatePermissions::new(true, false));
+        let replica = create_private_seq_replica_with(
+            Some(owner_keypair),
+            Some(SequencePrivatePolicy {
+                owner,
+                permissions: perms,
+            }),
+        );
+
+        let owner_perms = replica.my_permissions(owner);
+        assert!(owner_perms.is_owner && owner_perms.can_read && owner_perms.can_append);
+
+        let reader_perms = replica.my_permissions(reader);
+        assert!(!reader_perms.is_owner && reader_perms.can_read && !reader_perms.can_append);
+
+        let stranger_perms = replica.my_permissions(stranger);
+        assert!(!stranger_perms.can_read && !stranger_perms.can_append);
+
+        // anyone can read a public sequence
+        let public_replica = create_public_seq_replica_with(None, None);
+        let stranger_perms = public_replica.my_permissions(stranger);
+        assert!(stranger_perms.can_read && !stranger_perms.can_append);
+    }
+
+    #[test]
+    fn sequence_current_entry() -> anyhow::Result<()> {
+        let authority_keypair = Keypair::new_ed25519(&mut OsRng);
+        let mut replica = create_public_seq_replica_with(Some(authority_keypair.clone()), None);
+
+        // an empty sequence has no current entry
+        assert_eq!(replica.current_entry(None), Err(Error::NoSuchEntry));
+
+        let entry = b"value0".to_vec();
+        let op = sign_sequence_op(
+            replica.create_unsigned_append_op(entry.clone())?,
+            &authority_keypair,
+        )?;
+        replica.apply_op(op)?;
+        assert_eq!(replica.current_entry(None)?, &entry);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sequence_new_inheriting() -> anyhow::Result<()> {
+        let owner_keypair = Keypair::new_ed25519(&mut OsRng);
+        let owner = owner_keypair.public_key();
+        let user = Keypair::new_ed25519(&mut OsRng).public_key();
+
+        let mut perms = BTreeMap::default();
+        let _ = perms.insert(user, SequencePrivatePermissions::new(true, true));
+        let parent = create_private_seq_replica_with(
+            Some(owner_keypair),
+            Some(SequencePrivatePolicy {
+                owner,
+                permissions: perms.clone(),
+            }),
+        );
+
+        let child_address = SequenceAddress::Private {
+            name: XorName::random(),
+            tag: 43_000,
+        };
+        let child = Sequence::new_inheriting(user, user.to_string(), child_address, &parent)?;
+        assert_eq!(*child.address(), child_address);
+        assert_eq!(child.owner(), owner);
+        assert_eq!(child.replica_authority(), user);
+        assert_eq!(child.private_policy(Some(owner))?.permissions, perms);
+        assert_eq!(child.len(None)?, 0);
+
+        // a private parent's permissions can't be inherited by a public child
+        let public_address = SequenceAddress::Public {
+            name: XorName::random(),
+            tag: 43_000,
+        };
+        assert!(matches!(
+            Sequence::new_inheriting(user, user.to_string(), public_address, &parent),
+            Err(Error::InvalidOperation)
+        ));
+
+        Ok(())
+    }
+
     // Helpers for tests
 
     fn sign_sequence_op(
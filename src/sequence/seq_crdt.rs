@@ -18,6 +18,7 @@ use std::{
     fmt::{self, Debug, Display},
     hash::Hash,
 };
+use tiny_keccak::{Hasher, Sha3};
 
 /// CRDT Data operation applicable to other Sequence replica.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -77,6 +78,18 @@ where
         }
     }
 
+    /// Constructs a new 'SequenceCrdt' holding `entries`, appended by `actor`.
+    /// The entries are applied directly, as they come from a trusted snapshot of a replica
+    /// rather than from signed ops.
+    pub(crate) fn from_entries(actor: A, address: Address, policy: P, entries: Entries) -> Self {
+        let mut sequence = Self::new(actor, address, policy);
+        for entry in entries {
+            let op = sequence.data.append(entry, sequence.actor.clone());
+            sequence.data.apply(op);
+        }
+        sequence
+    }
+
     /// Returns the address.
     pub fn address(&self) -> &Address {
         &self.address
@@ -129,8 +142,13 @@ where
 
     /// Gets the entry at `index` if it exists.
     pub fn get(&self, index: Index) -> Option<&Entry> {
-        let i = to_absolute_index(index, self.len() as usize)?;
-        self.data.position(i)
+        let i = index.to_absolute(self.len())?;
+        self.data.position(i as usize)
+    }
+
+    /// Returns an iterator over the entries, in index order.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.data.iter()
     }
 
     /// Gets the last entry.
@@ -138,6 +156,21 @@ where
         self.data.last()
     }
 
+    /// Returns a checksum of the entries, in index order.
+    /// Each entry is length-prefixed, so entries can't be shifted into one another.
+    pub fn entries_checksum(&self) -> u64 {
+        let mut hasher = Sha3::v256();
+        for entry in self.data.iter() {
+            hasher.update(&(entry.len() as u64).to_le_bytes());
+            hasher.update(entry);
+        }
+        let mut output = [0; 32];
+        hasher.finalize(&mut output);
+        let mut checksum = [0; 8];
+        checksum.copy_from_slice(&output[..8]);
+        u64::from_le_bytes(checksum)
+    }
+
     /// Gets the Policy of the object.
     pub fn policy(&self) -> &P {
         &self.policy
@@ -145,19 +178,24 @@ where
 
     /// Gets a list of items which are within the given indices.
     /// Note the range of items is [start, end), i.e. the end index is not inclusive.
+    /// A `FromStart` end past the last item is clamped to it, while an end before the start
+    /// (including a `FromEnd` end before the first item) yields `None`.
     pub fn in_range(&self, start: Index, end: Index) -> Option<Entries> {
-        let count = self.len() as usize;
-        let start_index = to_absolute_index(start, count)?;
+        let count = self.len();
+        let start_index = start.to_absolute(count)?;
         if start_index >= count {
             return None;
         }
-        let end_index = to_absolute_index(end, count)?;
-        let items_to_take = end_index - start_index;
+        let end_index = match end {
+            Index::FromStart(index) => index.min(count),
+            Index::FromEnd(_) => end.to_absolute(count)?,
+        };
+        let items_to_take = end_index.checked_sub(start_index)? as usize;
 
         let entries = self
             .data
             .iter()
-            .skip(start_index)
+            .skip(start_index as usize)
             .take(items_to_take)
             .cloned()
             .collect::<Entries>();
@@ -165,13 +203,3 @@ where
         Some(entries)
     }
 }
-
-// Private helpers
-
-fn to_absolute_index(index: Index, count: usize) -> Option<usize> {
-    match index {
-        Index::FromStart(index) if (index as usize) <= count => Some(index as usize),
-        Index::FromStart(_) => None,
-        Index::FromEnd(index) => count.checked_sub(index as usize),
-    }
-}
@@ -0,0 +1,184 @@
+// Copyright 2021 MaidSafe.net limited.
+//
+// This SAFE Network Software is licensed to you under the MIT license <LICENSE-MIT
+// https://opensource.org/licenses/MIT> or the Modified BSD license <LICENSE-BSD
+// https://opensource.org/licenses/BSD-3-Clause>, at your option. This file may not be copied,
+// modified, or distributed except according to those terms. Please review the Licences for the
+// specific language governing permissions and limitations relating to use of the SAFE Network
+// Software.
+
+//! Portable snapshots of a Sequence, as produced by `Data::export`.
+//!
+//! Snapshots are JSON objects carrying a `version` field, and only hold the state needed to
+//! rebuild the Sequence, not its CRDT internals, so they survive changes to the in-memory
+//! types. Any change to the layout of a snapshot must add a new version, and `import` must
+//! keep accepting the older ones.
+
+use super::{
+    ActorType, Data, PrivatePermissions, PrivatePolicy, PrivateSeqData, PublicPermissions,
+    PublicPolicy, PublicSeqData, SeqData, User,
+};
+use crate::{Error, PublicKey, Result};
+use serde::{Deserialize, Serialize};
+use xor_name::XorName;
+
+/// Current version of the snapshot format.
+const SNAPSHOT_VERSION: u64 = 1;
+
+/// Version header shared by all versions of the snapshot format.
+#[derive(Deserialize)]
+struct Header {
+    version: u64,
+}
+
+/// Version 1 of the snapshot format.
+#[derive(Serialize, Deserialize)]
+struct SnapshotV1 {
+    version: u64,
+    /// PK which the ops of the replica are signed with.
+    authority: PublicKey,
+    /// Actor of the replica, re-appending the entries on import.
+    actor: ActorType,
+    /// Address, policy and entries.
+    sequence: SequenceV1,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind")]
+enum SequenceV1 {
+    Public {
+        name: XorName,
+        tag: u64,
+        owner: PublicKey,
+        // A list rather than a map, as JSON only has string keys.
+        permissions: Vec<(User, PublicPermissions)>,
+        /// Hex-encoded entries, in index order.
+        entries: Vec<String>,
+    },
+    Private {
+        name: XorName,
+        tag: u64,
+        owner: PublicKey,
+        permissions: Vec<(PublicKey, PrivatePermissions)>,
+        /// Hex-encoded entries, in index order.
+        entries: Vec<String>,
+    },
+}
+
+/// Encodes the replica as a snapshot of the current version.
+pub(super) fn export(data: &Data) -> Result<Vec<u8>> {
+    let (actor, sequence) = match &data.data {
+        SeqData::Public(seq) => {
+            let policy = seq.policy();
+            let address = seq.address();
+            (
+                seq.actor.clone(),
+                SequenceV1::Public {
+                    name: *address.name(),
+                    tag: address.tag(),
+                    owner: policy.owner,
+                    permissions: policy
+                        .permissions
+                        .iter()
+                        .map(|(user, perms)| (*user, *perms))
+                        .collect(),
+                    entries: seq.entries().map(hex::encode).collect(),
+                },
+            )
+        }
+        SeqData::Private(seq) => {
+            let policy = seq.policy();
+            let address = seq.address();
+            (
+                seq.actor.clone(),
+                SequenceV1::Private {
+                    name: *address.name(),
+                    tag: address.tag(),
+                    owner: policy.owner,
+                    permissions: policy
+                        .permissions
+                        .iter()
+                        .map(|(key, perms)| (*key, *perms))
+                        .collect(),
+                    entries: seq.entries().map(hex::encode).collect(),
+                },
+            )
+        }
+    };
+    let snapshot = SnapshotV1 {
+        version: SNAPSHOT_VERSION,
+        authority: data.authority,
+        actor,
+        sequence,
+    };
+    serde_json::to_vec(&snapshot).map_err(|e| Error::Serialisation(e.to_string()))
+}
+
+/// Rebuilds a replica from a snapshot of any supported version.
+pub(super) fn import(snapshot: &[u8]) -> Result<Data> {
+    let Header { version } = serde_json::from_slice(snapshot)
+        .map_err(|e| Error::FailedToParse(format!("Not a Sequence snapshot: {}", e)))?;
+    match version {
+        1 => import_v1(snapshot),
+        version => Err(Error::FailedToParse(format!(
+            "Unsupported Sequence snapshot version: {}",
+            version
+        ))),
+    }
+}
+
+fn import_v1(snapshot: &[u8]) -> Result<Data> {
+    let SnapshotV1 {
+        authority,
+        actor,
+        sequence,
+        ..
+    } = serde_json::from_slice(snapshot)
+        .map_err(|e| Error::FailedToParse(format!("Corrupted Sequence snapshot: {}", e)))?;
+
+    let data = match sequence {
+        SequenceV1::Public {
+            name,
+            tag,
+            owner,
+            permissions,
+            entries,
+        } => SeqData::Public(PublicSeqData::from_entries(
+            actor,
+            super::Address::Public { name, tag },
+            PublicPolicy {
+                owner,
+                permissions: permissions.into_iter().collect(),
+            },
+            decode_entries(entries)?,
+        )),
+        SequenceV1::Private {
+            name,
+            tag,
+            owner,
+            permissions,
+            entries,
+        } => SeqData::Private(PrivateSeqData::from_entries(
+            actor,
+            super::Address::Private { name, tag },
+            PrivatePolicy {
+                owner,
+                permissions: permissions.into_iter().collect(),
+            },
+            decode_entries(entries)?,
+        )),
+    };
+
+    Ok(Data { authority, data })
+}
+
+fn decode_entries(entries: Vec<String>) -> Result<Vec<Vec<u8>>> {
+    entries
+        .iter()
+        .map(|entry| {
+            hex::decode(entry).map_err(|e| {
+                Error::FailedToParse(format!("Corrupted Sequence snapshot entry: {}", e))
+            })
+        })
+        .collect()
+}
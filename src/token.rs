@@ -25,6 +25,9 @@ const TOKEN_TO_RAW_CONVERSION: u64 = 1_000_000_000;
 pub struct Token(u64);
 
 impl Token {
+    /// The maximum amount of Token that can be represented.
+    pub const MAX: Token = Token(u64::MAX);
+
     /// Type safe representation of zero Token.
     pub const fn zero() -> Self {
         Self(0)
@@ -40,6 +43,17 @@ impl Token {
         self.0
     }
 
+    /// Returns the fixed-width encoding of the amount: its nano tokens as a big-endian `u64`.
+    /// Unlike the general serialisation, the size never varies, e.g. for records in append logs.
+    pub const fn to_fixed_bytes(self) -> [u8; 8] {
+        self.0.to_be_bytes()
+    }
+
+    /// Reads an amount from its fixed-width encoding, as produced by `to_fixed_bytes`.
+    pub const fn from_fixed_bytes(bytes: [u8; 8]) -> Self {
+        Self(u64::from_be_bytes(bytes))
+    }
+
     /// Computes `self + rhs`, returning `None` if overflow occurred.
     pub fn checked_add(self, rhs: Token) -> Option<Token> {
         self.0.checked_add(rhs.0).map(Self::from_nano)
@@ -49,18 +63,85 @@ impl Token {
     pub fn checked_sub(self, rhs: Token) -> Option<Token> {
         self.0.checked_sub(rhs.0).map(Self::from_nano)
     }
+
+    /// Computes `self * rhs` (in nano tokens), returning `None` if overflow occurred.
+    pub fn checked_mul(self, rhs: u64) -> Option<Token> {
+        self.0.checked_mul(rhs).map(Self::from_nano)
+    }
+
+    /// Computes `self + rhs`, saturating at `Token::MAX`.
+    pub fn saturating_add(self, rhs: Token) -> Token {
+        Self::from_nano(self.0.saturating_add(rhs.0))
+    }
+
+    /// Computes `self - rhs`, saturating at zero.
+    pub fn saturating_sub(self, rhs: Token) -> Token {
+        Self::from_nano(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns the balance resulting from crediting `amount` to `self`,
+    /// or `Err(Error::ExcessiveValue)` if it would exceed the maximum value.
+    pub fn apply_credit(self, amount: Token) -> Result<Token> {
+        self.checked_add(amount).ok_or(Error::ExcessiveValue)
+    }
+
+    /// Returns the balance resulting from debiting `amount` from `self`,
+    /// or `Err(Error::InsufficientBalance)` with both amounts if `self` is lower than `amount`.
+    pub fn apply_debit(self, amount: Token) -> Result<Token> {
+        self.checked_sub(amount).ok_or(Error::InsufficientBalance {
+            available: self,
+            required: amount,
+        })
+    }
+
+    /// Returns the amount formatted for display, with the units grouped in thousands
+    /// by `separator` (e.g. "1,000.500000000"), keeping the nano fraction exact.
+    ///
+    /// This is a presentation format only: it is not parsed back by `Token::from_str`.
+    pub fn to_grouped_string(&self, separator: char) -> String {
+        let unit = (self.0 / TOKEN_TO_RAW_CONVERSION).to_string();
+        let remainder = self.0 % TOKEN_TO_RAW_CONVERSION;
+
+        // a separator goes before every digit followed by a multiple of 3 digits
+        let offset = unit.len() % 3;
+        let mut grouped = String::with_capacity(unit.len() + unit.len() / 3);
+        for (i, digit) in unit.chars().enumerate() {
+            if i > 0 && i % 3 == offset {
+                grouped.push(separator);
+            }
+            grouped.push(digit);
+        }
+
+        format!("{}.{:09}", grouped, remainder)
+    }
 }
 
 impl FromStr for Token {
     type Err = Error;
 
+    /// Parses a decimal amount of Token, e.g. "1", "1.5" or ".000000001".
+    /// Signs and more than 9 (non-zero) decimal places are rejected.
     fn from_str(value_str: &str) -> Result<Self> {
+        let is_numeric = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
         let mut itr = value_str.splitn(2, '.');
+        let units_str = itr.next().unwrap_or_default();
+        let remainder_str = itr.next();
+
         let converted_units = {
-            let units = itr
-                .next()
-                .and_then(|s| s.parse::<u64>().ok())
-                .ok_or_else(|| Error::FailedToParse("Can't parse token units".to_string()))?;
+            if !is_numeric(units_str)
+                || (units_str.is_empty() && remainder_str.unwrap_or_default().is_empty())
+            {
+                return Err(Error::FailedToParse("Can't parse token units".to_string()));
+            }
+            // Only digits are left, so parsing can only fail on overflow.
+            let units = if units_str.is_empty() {
+                0
+            } else {
+                units_str
+                    .parse::<u64>()
+                    .map_err(|_| Error::ExcessiveValue)?
+            };
 
             units
                 .checked_mul(TOKEN_TO_RAW_CONVERSION)
@@ -68,37 +149,52 @@ impl FromStr for Token {
         };
 
         let remainder = {
-            let remainder_str = itr.next().unwrap_or_default().trim_end_matches('0');
+            let remainder_str = remainder_str.unwrap_or_default().trim_end_matches('0');
 
+            if !is_numeric(remainder_str) {
+                return Err(Error::FailedToParse(
+                    "Can't parse token remainder".to_string(),
+                ));
+            }
             if remainder_str.is_empty() {
                 0
             } else {
-                let parsed_remainder = remainder_str
-                    .parse::<u64>()
-                    .map_err(|_| Error::FailedToParse("Can't parse token remainder".to_string()))?;
-
                 let remainder_conversion = TOKEN_TO_RAW_POWER_OF_10_CONVERSION
                     .checked_sub(remainder_str.len() as u32)
                     .ok_or(Error::LossOfPrecision)?;
+                let parsed_remainder = remainder_str
+                    .parse::<u64>()
+                    .map_err(|_| Error::FailedToParse("Can't parse token remainder".to_string()))?;
                 parsed_remainder * 10_u64.pow(remainder_conversion)
             }
         };
 
-        Ok(Self::from_nano(converted_units + remainder))
+        converted_units
+            .checked_add(remainder)
+            .map(Self::from_nano)
+            .ok_or(Error::ExcessiveValue)
     }
 }
 
 impl Debug for Token {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        Display::fmt(self, formatter)
+        // Both forms are shown so the nano value can't be misread as whole tokens.
+        write!(formatter, "Token({} SNT / {} nano)", self, self.0)
     }
 }
 
 impl Display for Token {
+    /// Writes the canonical decimal form, without trailing zeros, e.g. "1", "1.5",
+    /// which `Token::from_str` parses back to the same value.
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         let unit = self.0 / TOKEN_TO_RAW_CONVERSION;
         let remainder = self.0 % TOKEN_TO_RAW_CONVERSION;
-        write!(formatter, "{}.{}", unit, format!("{:09}", remainder))
+        if remainder == 0 {
+            write!(formatter, "{}", unit)
+        } else {
+            let remainder = format!("{:09}", remainder);
+            write!(formatter, "{}.{}", unit, remainder.trim_end_matches('0'))
+        }
     }
 }
 
@@ -113,6 +209,9 @@ mod tests {
         assert_eq!(Token(0), Token::from_str("0.")?);
         assert_eq!(Token(0), Token::from_str("0.0")?);
         assert_eq!(Token(1), Token::from_str("0.000000001")?);
+        assert_eq!(Token(1), Token::from_str(".000000001")?);
+        assert_eq!(Token(500_000_000), Token::from_str(".5")?);
+        assert_eq!(Token::MAX, Token::from_str("18446744073.709551615")?);
         assert_eq!(Token(1_000_000_000), Token::from_str("1")?);
         assert_eq!(Token(1_000_000_000), Token::from_str("1.")?);
         assert_eq!(Token(1_000_000_000), Token::from_str("1.0")?);
@@ -148,22 +247,104 @@ mod tests {
             )),
             Token::from_str("0.0.0")
         );
+        assert_eq!(
+            Err(Error::FailedToParse(
+                "Can't parse token remainder".to_string()
+            )),
+            Token::from_str("1.2.3")
+        );
+        assert_eq!(
+            Err(Error::FailedToParse("Can't parse token units".to_string())),
+            Token::from_str("abc")
+        );
+        for malformed in &["", ".", "+1", "-1", " 1", "1 ", "1.+5", "1.-5", "0x10"] {
+            assert!(Token::from_str(malformed).is_err(), "{}", malformed);
+        }
         assert_eq!(Err(Error::LossOfPrecision), Token::from_str("0.0000000009"));
         assert_eq!(Err(Error::ExcessiveValue), Token::from_str("18446744074"));
+        assert_eq!(
+            Err(Error::ExcessiveValue),
+            Token::from_str("18446744073.709551616")
+        );
+        assert_eq!(
+            Err(Error::ExcessiveValue),
+            Token::from_str("184467440730000000000")
+        );
         Ok(())
     }
 
     #[test]
     fn display() {
-        assert_eq!("0.000000000", format!("{}", Token(0)));
+        assert_eq!("0", format!("{}", Token(0)));
         assert_eq!("0.000000001", format!("{}", Token(1)));
-        assert_eq!("0.000000010", format!("{}", Token(10)));
-        assert_eq!("1.000000000", format!("{}", Token(1_000_000_000)));
+        assert_eq!("0.00000001", format!("{}", Token(10)));
+        assert_eq!("1", format!("{}", Token(1_000_000_000)));
         assert_eq!("1.000000001", format!("{}", Token(1_000_000_001)));
+        assert_eq!("1.5", format!("{}", Token(1_500_000_000)));
         assert_eq!(
-            "4294967295.000000000",
+            "4294967295",
             format!("{}", Token(4_294_967_295_000_000_000))
         );
+        assert_eq!(
+            "4294967295.999999999",
+            format!("{}", Token(4_294_967_295_999_999_999))
+        );
+    }
+
+    #[test]
+    fn display_from_str_round_trip() -> Result<()> {
+        for nano in &[
+            0,
+            1,
+            10,
+            1_000_000_000,
+            1_500_000_000,
+            999_999_999,
+            u64::MAX,
+        ] {
+            let token = Token(*nano);
+            assert_eq!(token, Token::from_str(&token.to_string())?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn debug() {
+        assert_eq!(
+            "Token(1.5 SNT / 1500000000 nano)",
+            format!("{:?}", Token(1_500_000_000))
+        );
+        assert_eq!("Token(0 SNT / 0 nano)", format!("{:?}", Token(0)));
+    }
+
+    #[test]
+    fn to_grouped_string() {
+        assert_eq!("0.000000000", Token(0).to_grouped_string(','));
+        assert_eq!(
+            "999.000000001",
+            Token(999_000_000_001).to_grouped_string(',')
+        );
+        assert_eq!(
+            "1,000.500000000",
+            Token(1_000_500_000_000).to_grouped_string(',')
+        );
+        assert_eq!(
+            "18 446 744 073.709551615",
+            Token(u64::MAX).to_grouped_string(' ')
+        );
+    }
+
+    #[test]
+    fn fixed_bytes() {
+        let cases = [
+            (Token::zero(), [0; 8]),
+            (Token(1_500_000_000), [0, 0, 0, 0, 0x59, 0x68, 0x2f, 0x00]),
+            (Token::MAX, [0xff; 8]),
+        ];
+        for (token, bytes) in cases.iter() {
+            assert_eq!(token.to_fixed_bytes(), *bytes);
+            assert_eq!(Token::from_fixed_bytes(*bytes), *token);
+        }
     }
 
     #[test]
@@ -176,4 +357,59 @@ mod tests {
         assert_eq!(None, Token(0).checked_sub(Token(u64::MAX)));
         assert_eq!(None, Token(10).checked_sub(Token(11)));
     }
+
+    #[test]
+    fn checked_mul() {
+        assert_eq!(Some(Token(0)), Token(0).checked_mul(u64::MAX));
+        assert_eq!(Some(Token(0)), Token::MAX.checked_mul(0));
+        assert_eq!(Some(Token::MAX), Token::MAX.checked_mul(1));
+        assert_eq!(
+            Some(Token(3_000_000_000)),
+            Token(1_000_000_000).checked_mul(3)
+        );
+        assert_eq!(None, Token::MAX.checked_mul(2));
+        assert_eq!(None, Token(u64::MAX / 2 + 1).checked_mul(2));
+    }
+
+    #[test]
+    fn saturating_add_sub() {
+        assert_eq!(Token(3), Token(1).saturating_add(Token(2)));
+        assert_eq!(Token::MAX, Token::MAX.saturating_add(Token(1)));
+        assert_eq!(Token::MAX, Token(u64::MAX - 1).saturating_add(Token::MAX));
+
+        assert_eq!(Token(1), Token(3).saturating_sub(Token(2)));
+        assert_eq!(Token::zero(), Token(0).saturating_sub(Token(1)));
+        assert_eq!(Token::zero(), Token(10).saturating_sub(Token::MAX));
+    }
+
+    #[test]
+    fn apply_credit_debit() {
+        assert_eq!(Ok(Token(0)), Token(10).apply_debit(Token(10)));
+        assert_eq!(Ok(Token(10)), Token(10).apply_debit(Token(0)));
+        assert_eq!(Ok(Token(3)), Token(10).apply_debit(Token(7)));
+        assert_eq!(
+            Err(Error::InsufficientBalance {
+                available: Token(10),
+                required: Token(11)
+            }),
+            Token(10).apply_debit(Token(11))
+        );
+        match Token(10).apply_debit(Token(25)) {
+            Err(Error::InsufficientBalance {
+                available,
+                required,
+            }) => assert_eq!(required.checked_sub(available), Some(Token(15))),
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        assert_eq!(Ok(Token(17)), Token(10).apply_credit(Token(7)));
+        assert_eq!(
+            Ok(Token(u64::MAX)),
+            Token(u64::MAX - 1).apply_credit(Token(1))
+        );
+        assert_eq!(
+            Err(Error::ExcessiveValue),
+            Token(u64::MAX).apply_credit(Token(1))
+        );
+    }
 }
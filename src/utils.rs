@@ -9,6 +9,7 @@
 
 use crate::errors::convert_bincode_error;
 use crate::{Error, Result};
+use bincode::Options;
 use multibase::{self, Base};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
@@ -25,6 +26,61 @@ where
     bincode::deserialize(bytes).map_err(convert_bincode_error)
 }
 
+/// Format byte prefixed to the output of `serialise_compressed`: bincode, then LZ4 block
+/// compression with the uncompressed size prepended.
+const COMPRESSED_LZ4_V1: u8 = 0xC1;
+
+// LZ4 can't compress by more than this ratio, which bounds the size a decoder may allocate.
+const LZ4_MAX_COMPRESSION_RATIO: usize = 255;
+
+/// Serialises `data` with bincode and compresses the result, prefixed with a format byte.
+pub fn serialise_compressed<T: Serialize>(data: &T) -> Result<Vec<u8>> {
+    let compressed = lz4_flex::compress_prepend_size(&serialise(data)?);
+    let mut bytes = Vec::with_capacity(1 + compressed.len());
+    bytes.push(COMPRESSED_LZ4_V1);
+    bytes.extend(compressed);
+    Ok(bytes)
+}
+
+/// Decompresses and deserialises `bytes`, as produced by `serialise_compressed`.
+/// Returns `Err(Error::FailedToParse)` if the bytes aren't in that format.
+pub fn deserialise_compressed<T: DeserializeOwned>(bytes: &[u8]) -> Result<T> {
+    let compressed = match bytes.split_first() {
+        Some((&COMPRESSED_LZ4_V1, compressed)) => compressed,
+        Some((format, _)) => {
+            return Err(Error::FailedToParse(format!(
+                "Unknown compression format: {:#x}",
+                format
+            )))
+        }
+        None => return Err(Error::FailedToParse("Empty compressed data".to_string())),
+    };
+    let (size, block) = lz4_flex::block::uncompressed_size(compressed)
+        .map_err(|e| Error::FailedToParse(e.to_string()))?;
+    // Reject sizes which can't be genuine before allocating for them.
+    if size > block.len().saturating_mul(LZ4_MAX_COMPRESSION_RATIO) {
+        return Err(Error::FailedToParse(format!(
+            "Invalid uncompressed size: {}",
+            size
+        )));
+    }
+    let decompressed =
+        lz4_flex::decompress(block, size).map_err(|e| Error::FailedToParse(e.to_string()))?;
+    deserialise(&decompressed).map_err(|e| Error::FailedToParse(e.to_string()))
+}
+
+/// Serialises `data` into JSON, e.g. for debugging tools and non-Rust clients.
+///
+/// Keys and addresses are serialised as z-base-32 strings in JSON, rather than byte arrays.
+pub fn to_json<T: Serialize>(data: &T) -> Result<String> {
+    serde_json::to_string(data).map_err(|e| Error::Serialisation(e.to_string()))
+}
+
+/// Deserialises `data` from JSON, as produced by `to_json`.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T> {
+    serde_json::from_str(json).map_err(|e| Error::FailedToParse(e.to_string()))
+}
+
 /// Wrapper for z-Base-32 multibase::encode.
 pub(crate) fn encode<T: Serialize>(data: &T) -> Result<String> {
     let bytes = serialise(&data)?;
@@ -41,5 +97,49 @@ pub(crate) fn decode<I: AsRef<str>, O: DeserializeOwned>(encoded: I) -> Result<O
             base
         )));
     }
-    deserialise(&decoded).map_err(|e| Error::FailedToParse(e.to_string()))
+    // Unlike `deserialise`, reject trailing bytes so that only strings which round-trip are
+    // accepted.
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .deserialize(&decoded)
+        .map_err(|e| Error::FailedToParse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressed_serialisation() -> Result<()> {
+        let blob = b"0123456789".repeat(1024);
+
+        let compressed = serialise_compressed(&blob)?;
+        assert!(compressed.len() < serialise(&blob)?.len() / 10);
+        let decompressed: Vec<u8> = deserialise_compressed(&compressed)?;
+        assert_eq!(decompressed, blob);
+
+        // data which wasn't compressed
+        assert!(matches!(
+            deserialise_compressed::<Vec<u8>>(&serialise(&blob)?),
+            Err(Error::FailedToParse(_))
+        ));
+        assert!(matches!(
+            deserialise_compressed::<Vec<u8>>(&[]),
+            Err(Error::FailedToParse(_))
+        ));
+        // a bogus uncompressed size
+        let mut bogus = compressed.clone();
+        bogus[1..5].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            deserialise_compressed::<Vec<u8>>(&bogus),
+            Err(Error::FailedToParse(_))
+        ));
+        // truncated
+        assert!(matches!(
+            deserialise_compressed::<Vec<u8>>(&compressed[..compressed.len() - 1]),
+            Err(Error::FailedToParse(_))
+        ));
+
+        Ok(())
+    }
 }